@@ -1,6 +1,6 @@
 use std::{collections::HashMap, ops::Range};
 
-use wca_oauth::Datelike;
+use wca_oauth::{Datelike, EventId};
 
 const ANONYMOUS: [usize; 0] = [];
 const AGE_GROUPS: [Range<i32>; 6] =[0..12, 12..14, 14..18, 18..22, 22..40, 40..1000];
@@ -11,17 +11,21 @@ fn main() {
         .filter(|person| person.registrant_id.is_some())
         .filter(|person| person.country_iso_2 == "DK")
         .filter(|person| !ANONYMOUS.contains(&person.registrant_id.unwrap()))
-        .map(|person| {
-            let bd = person.birthdate;
+        .filter_map(|person| {
+            // The public wcif does not contain birthdates.
+            let Some(bd) = person.birthdate else {
+                eprintln!("{} has no birthdate, use the private wcif", person.name);
+                return None;
+            };
             let year = bd.year();
             let month = bd.month();
             let day = bd.day();
             let age = 2023 - year - if month > 9 || (month == 9 && day > 10) { 1 } else { 0 };
-            (person.registrant_id.unwrap(), (age, &person.name))
+            Some((person.registrant_id.unwrap(), (age, &person.name)))
         })
         .collect::<HashMap<_, _>>();
     wcif.events_iter()
-        .find(|event| event.id == "333")
+        .find(|event| event.id == EventId::E333)
         .expect("It seems that 3x3x3 is not hosted at this competition")
         .rounds
         .iter()
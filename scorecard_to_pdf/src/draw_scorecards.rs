@@ -4,8 +4,9 @@ use crate::scorecard::MaybeScorecard;
 use crate::scorecard_generator::ScorecardGenerator;
 use crate::TimeLimit;
 use crate::scorecard_generator::{Alignment::*, Weight::*};
+use wca_oauth::EventId;
 
-pub fn draw_scorecard(generator: &mut ScorecardGenerator, scorecard: &MaybeScorecard, map: &HashMap<usize, String>, limits: &HashMap<&str, TimeLimit>, language: &Language) {
+pub fn draw_scorecard(generator: &mut ScorecardGenerator, scorecard: &MaybeScorecard, map: &HashMap<usize, String>, limits: &HashMap<EventId, TimeLimit>, language: &Language) {
     let get_event = get_event_func(language);
     //Competiton
     let name = generator.get_competition_name().to_string();
@@ -23,10 +24,7 @@ pub fn draw_scorecard(generator: &mut ScorecardGenerator, scorecard: &MaybeScore
     generator.draw_square(15.0, 15.0, 85.0, 5.5);
     generator.write(scorecard.name(map).unwrap_or(""), 16.0, 19.0, 10.0, Left, Normal);
 
-    let attempts_amount = match scorecard.event() {
-        "666" | "777" | "333mbf" | "444bf" | "555bf" => 3,
-        _ => 5
-    };
+    let attempts_amount = scorecard.attempts();
 
     let height = 8.2;
     let distance = 8.8;
@@ -60,7 +58,7 @@ pub fn draw_scorecard(generator: &mut ScorecardGenerator, scorecard: &MaybeScore
         TimeLimit::Single(z) => format!("{}: {}", language.time_limit, time_string(*z)),
         TimeLimit::Cumulative(z) => format!("{}: {}", language.cumulative_limit, time_string(*z)),
         TimeLimit::Cutoff(x, z) => format!("{}: {}, {}: {}", language.curoff, time_string(*x), language.time_limit, time_string(*z)),
-        TimeLimit::SharedCumulative(z, vec) => format!("{}: {} {} {}", language.cumulative_limit, time_string(*z), language.for_scl, vec.iter().map(|x|get_event(x)).collect::<Vec<_>>().join(&format!(" {} ", language.and_scl))),
+        TimeLimit::SharedCumulative(z, vec) => format!("{}: {} {} {}", language.cumulative_limit, time_string(*z), language.for_scl, vec.iter().map(|x|get_event(Some(x))).collect::<Vec<_>>().join(&format!(" {} ", language.and_scl))),
         TimeLimit::Multi => language.multi_tl.to_owned(),
         TimeLimit::None => format!("")
     };
@@ -86,27 +84,27 @@ fn time_string(mut z: usize) -> String {
     }
 }
 
-fn get_event_func<'a>(language: &'a Language) -> impl Fn(&str) -> &'a str {
+fn get_event_func<'a>(language: &'a Language) -> impl Fn(Option<&EventId>) -> &'a str {
     |x| match x {
-        "" => "___________________________",
-        "333" => &language.e333,
-        "444" => &language.e444,
-        "555" => &language.e555,
-        "666" => &language.e666,
-        "777" => &language.e777,
-        "222" => &language.e222,
-        "333oh" => &language.e333oh,
-        "333fm" => "Filter out FMC",
-        "333bf" => &language.e333bf,
-        "pyram" => &language.epyram,
-        "333mbf" => &language.e333mbf,
-        "minx" => &language.eminx,
-        "clock" => &language.eclock,
-        "444bf" => &language.e444bf,
-        "555bf" => &language.e555bf,
-        "skewb" => &language.eskewb,
-        "sq1" => &language.esq1,
-	"fto" => "Face Turning Octahedron", // We are prepared for the future! (Just in case)
-        _ => "Please fix your csv"
+        None => "___________________________",
+        Some(EventId::E333) => &language.e333,
+        Some(EventId::E444) => &language.e444,
+        Some(EventId::E555) => &language.e555,
+        Some(EventId::E666) => &language.e666,
+        Some(EventId::E777) => &language.e777,
+        Some(EventId::E222) => &language.e222,
+        Some(EventId::E333oh) => &language.e333oh,
+        Some(EventId::E333fm) => "Filter out FMC",
+        Some(EventId::E333bf) => &language.e333bf,
+        Some(EventId::Pyram) => &language.epyram,
+        Some(EventId::E333mbf) => &language.e333mbf,
+        Some(EventId::Minx) => &language.eminx,
+        Some(EventId::Clock) => &language.eclock,
+        Some(EventId::E444bf) => &language.e444bf,
+        Some(EventId::E555bf) => &language.e555bf,
+        Some(EventId::Skewb) => &language.eskewb,
+        Some(EventId::Sq1) => &language.esq1,
+	Some(EventId::Other(id)) if id == "fto" => "Face Turning Octahedron", // We are prepared for the future! (Just in case)
+        Some(EventId::Other(_)) => "Please fix your csv"
     }
 }
@@ -5,3 +5,4 @@ mod draw_scorecards;
 mod scorecard_generator;
 pub use scorecard::{scorecards_to_pdf, Scorecard, TimeLimit, Return, blank_scorecard_page};
 pub use language::Language;
+pub use wca_oauth::{EventId, RoundFormat};
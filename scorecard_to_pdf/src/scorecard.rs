@@ -4,11 +4,15 @@ use std::io::Write;
 use crate::language::Language;
 use crate::draw_scorecards::draw_scorecard;
 use crate::scorecard_generator::ScorecardGenerator;
+use wca_oauth::{EventId, RoundFormat};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Scorecard<'a> {
-    pub event: &'a str,
+    pub event: &'a EventId,
     pub round: usize,
+    /// The format of the round, which decides the number of attempts. When not known, the
+    /// preferred format of the event is assumed.
+    pub format: Option<RoundFormat>,
     pub group: usize,
     pub station: Option<usize>,
     pub id: Option<usize>,
@@ -18,7 +22,7 @@ pub struct Scorecard<'a> {
 pub enum TimeLimit {
     Single(usize),
     Cumulative(usize),
-    SharedCumulative(usize, Vec<String>),
+    SharedCumulative(usize, Vec<EventId>),
     Cutoff(usize, usize),
     Multi,
     None
@@ -43,8 +47,13 @@ impl<'a> MaybeScorecard<'a> {
         }
     }
 
-    pub fn event(&self) -> &str {
-        self.internal_or_default(|s| s.event, "")
+    pub fn event(&self) -> Option<&EventId> {
+        self.internal_or_default(|s| Some(s.event), None)
+    }
+
+    /// The number of attempt rows on the scorecard.
+    pub fn attempts(&self) -> usize {
+        self.internal_or_default(|s| s.format.and_then(|format| format.attempts()).unwrap_or_else(|| s.event.default_attempts()), 5)
     }
 
     pub fn round(&self) -> String {
@@ -67,12 +76,12 @@ impl<'a> MaybeScorecard<'a> {
         self.internal_or_default(|s| s.id.map(|id| map[&id].as_str()), Some(""))
     }
 
-    pub fn limit(&'a self, limit: &'a HashMap<&str, TimeLimit>) -> &'a TimeLimit {
+    pub fn limit(&'a self, limit: &'a HashMap<EventId, TimeLimit>) -> &'a TimeLimit {
         self.internal_or_default(|s| &limit.get(s.event).unwrap_or(&TimeLimit::None), &TimeLimit::None)
     }
 }
 
-pub fn scorecards_to_pdf(scorecards: Vec<Scorecard>, competition: &str, map: &HashMap<usize, String>, limits: &HashMap<&str, TimeLimit>, language: Language) -> Return {
+pub fn scorecards_to_pdf(scorecards: Vec<Scorecard>, competition: &str, map: &HashMap<usize, String>, limits: &HashMap<EventId, TimeLimit>, language: Language) -> Return {
     let mut buckets = HashMap::new();
     for scorecard in scorecards {
         let key = scorecard.stage;
@@ -105,7 +114,7 @@ pub fn scorecards_to_pdf(scorecards: Vec<Scorecard>, competition: &str, map: &Ha
     }
 }
 
-pub fn scorecards_to_pdf_internal(scorecards: Vec<Scorecard>, competition: &str, map: &HashMap<usize, String>, limits: &HashMap<&str, TimeLimit>, language: &Language) -> PdfDocumentReference {
+pub fn scorecards_to_pdf_internal(scorecards: Vec<Scorecard>, competition: &str, map: &HashMap<usize, String>, limits: &HashMap<EventId, TimeLimit>, language: &Language) -> PdfDocumentReference {
     let mut scorecard_generator = ScorecardGenerator::new(competition);
     let mut scorecards: Vec<MaybeScorecard> = scorecards.into_iter().map(|scorecard| MaybeScorecard::Normal(scorecard)).collect();
     while scorecards.len() % 6 != 0 {
@@ -0,0 +1,257 @@
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use reqwest::Url;
+use sha2::{Digest, Sha256};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+use crate::{ApiResult, WcaApiError, WCA_URL};
+
+/// How long `RedirectListener::wait_for_code` waits for the user by default.
+const TIMEOUT: Duration = Duration::from_secs(5 * 60);
+/// How long a connection to the listener may take to send its request by default.
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The parameters of a request to `/oauth/authorize`. The user opens `url()` in a browser and
+/// the WCA redirects back to the redirect uri with an authorization code.
+#[derive(Debug, Clone)]
+pub struct AuthorizationRequest {
+	client_id: String,
+	redirect_uri: String,
+	auth_url: String,
+	scopes: Vec<String>,
+	state: String,
+	code_verifier: Option<String>,
+}
+
+impl AuthorizationRequest {
+	pub fn new(client_id: String, redirect_uri: String) -> AuthorizationRequest {
+		AuthorizationRequest {
+			client_id,
+			redirect_uri,
+			auth_url: WCA_URL.to_string(),
+			scopes: vec!["public".to_string(), "manage_competitions".to_string()],
+			state: random_string(32),
+			code_verifier: None,
+		}
+	}
+
+	pub fn auth_url(mut self, auth_url: impl Into<String>) -> Self {
+		self.auth_url = auth_url.into().trim_end_matches('/').to_string();
+		self
+	}
+
+	/// Replaces the requested scopes. Defaults to `public manage_competitions`.
+	pub fn scopes<S: Into<String>>(mut self, scopes: impl IntoIterator<Item = S>) -> Self {
+		self.scopes = scopes.into_iter().map(Into::into).collect();
+		self
+	}
+
+	/// Protects the authorization code with PKCE. The code verifier is sent along when the code is
+	/// exchanged for tokens.
+	pub fn pkce(mut self) -> Self {
+		self.code_verifier = Some(random_string(64));
+		self
+	}
+
+	pub fn state(&self) -> &str {
+		&self.state
+	}
+
+	pub fn code_verifier(&self) -> Option<&str> {
+		self.code_verifier.as_deref()
+	}
+
+	/// The url the user has to visit to authorize the application. Fails with
+	/// `WcaApiError::Authorization` if the auth url is not a valid url.
+	pub fn url(&self) -> ApiResult<Url> {
+		let mut url = Url::parse(&format!("{}/oauth/authorize", self.auth_url))
+			.map_err(|e| WcaApiError::Authorization(format!("invalid auth url: {e}")))?;
+		url.query_pairs_mut()
+			.append_pair("client_id", &self.client_id)
+			.append_pair("redirect_uri", &self.redirect_uri)
+			.append_pair("response_type", "code")
+			.append_pair("scope", &self.scopes.join(" "))
+			.append_pair("state", &self.state);
+		if let Some(code_verifier) = &self.code_verifier {
+			let challenge = URL_SAFE_NO_PAD.encode(Sha256::digest(code_verifier.as_bytes()));
+			url.query_pairs_mut()
+				.append_pair("code_challenge", &challenge)
+				.append_pair("code_challenge_method", "S256");
+		}
+		Ok(url)
+	}
+
+	/// Starts listening on the redirect uri, which has to point to the loopback interface, e.g.
+	/// `http://localhost:8080/callback`. Do this before sending the user to `url()`. Fails without
+	/// listening if the auth url or the redirect uri is invalid.
+	pub async fn listen(&self) -> ApiResult<RedirectListener<'_>> {
+		self.url()?;
+		let redirect_uri = Url::parse(&self.redirect_uri)
+			.map_err(|e| WcaApiError::Authorization(format!("invalid redirect uri: {e}")))?;
+		let host = match redirect_uri.host_str() {
+			Some("localhost") | Some("127.0.0.1") => "127.0.0.1",
+			Some("[::1]") => "::1",
+			_ => {
+				return Err(WcaApiError::Authorization(format!(
+					"redirect uri {} does not point to the loopback interface",
+					self.redirect_uri
+				)))
+			}
+		};
+		let port = redirect_uri.port_or_known_default().unwrap_or(80);
+		let listener = TcpListener::bind((host, port)).await?;
+		Ok(RedirectListener {
+			request: self,
+			path: redirect_uri.path().to_string(),
+			listener,
+			timeout: TIMEOUT,
+			read_timeout: READ_TIMEOUT,
+		})
+	}
+}
+
+/// A temporary http server on the redirect uri, waiting for the WCA to redirect the user back.
+#[derive(Debug)]
+pub struct RedirectListener<'a> {
+	request: &'a AuthorizationRequest,
+	path: String,
+	listener: TcpListener,
+	timeout: Duration,
+	read_timeout: Duration,
+}
+
+impl RedirectListener<'_> {
+	/// The address the listener is bound to. If the redirect uri has port 0, a free port is picked
+	/// when listening, which can be found here.
+	pub fn local_addr(&self) -> ApiResult<SocketAddr> {
+		Ok(self.listener.local_addr()?)
+	}
+
+	/// How long to wait for the redirect in total. Defaults to 5 minutes.
+	pub fn timeout(mut self, timeout: Duration) -> Self {
+		self.timeout = timeout;
+		self
+	}
+
+	/// How long a single connection may take to send its request before it is dropped, so an idle
+	/// connection, e.g. one a browser opened in advance, does not block the redirect for long.
+	/// Defaults to 5 seconds.
+	pub fn read_timeout(mut self, read_timeout: Duration) -> Self {
+		self.read_timeout = read_timeout;
+		self
+	}
+
+	/// Waits for the redirect and returns the authorization code after validating the state.
+	/// Fails with `WcaApiError::Authorization` if no redirect arrives within the timeout.
+	pub async fn wait_for_code(self) -> ApiResult<String> {
+		let timeout = self.timeout;
+		tokio::time::timeout(timeout, self.accept_redirect())
+			.await
+			.unwrap_or_else(|_| {
+				Err(WcaApiError::Authorization(format!(
+					"no redirect was received within {timeout:?}"
+				)))
+			})
+	}
+
+	async fn accept_redirect(self) -> ApiResult<String> {
+		loop {
+			let (mut stream, _) = self.listener.accept().await?;
+			let read = tokio::time::timeout(self.read_timeout, read_request_target(&mut stream));
+			let target = match read.await {
+				Ok(Ok(Some(target))) => target,
+				// The connection was idle, closed early or not a GET request.
+				Ok(Ok(None)) | Ok(Err(_)) | Err(_) => continue,
+			};
+			let url = Url::parse(&format!("http://localhost{target}"))
+				.map_err(|e| WcaApiError::Authorization(format!("invalid redirect: {e}")))?;
+			if url.path() != self.path {
+				// Failing to answer another request, e.g. for the favicon, is not fatal.
+				let _ = respond(&mut stream, "404 Not Found", "Not found").await;
+				continue;
+			}
+
+			let param = |name: &str| {
+				url.query_pairs()
+					.find(|(key, _)| key == name)
+					.map(|(_, value)| value.into_owned())
+			};
+			let result = if let Some(error) = param("error") {
+				Err(format!(
+					"authorization was denied: {}",
+					param("error_description").unwrap_or(error)
+				))
+			} else if param("state").as_deref() != Some(self.request.state()) {
+				Err("state of the redirect does not match the request".to_string())
+			} else if let Some(code) = param("code") {
+				Ok(code)
+			} else {
+				Err("redirect did not contain an authorization code".to_string())
+			};
+
+			return match result {
+				Ok(code) => {
+					respond(
+						&mut stream,
+						"200 OK",
+						"Logged in. You can close this window.",
+					)
+					.await?;
+					Ok(code)
+				}
+				Err(e) => {
+					respond(&mut stream, "400 Bad Request", &e).await?;
+					Err(WcaApiError::Authorization(e))
+				}
+			};
+		}
+	}
+}
+
+/// Reads the head of an http request and returns the request target of its request line.
+async fn read_request_target(stream: &mut TcpStream) -> ApiResult<Option<String>> {
+	let mut buf = Vec::new();
+	let mut chunk = [0; 1024];
+	while !buf.windows(4).any(|w| w == b"\r\n\r\n") && buf.len() < 16 * 1024 {
+		let read = stream.read(&mut chunk).await?;
+		if read == 0 {
+			break;
+		}
+		buf.extend_from_slice(&chunk[..read]);
+	}
+	let head = String::from_utf8_lossy(&buf);
+	let mut request_line = head.lines().next().unwrap_or("").split_whitespace();
+	Ok(match (request_line.next(), request_line.next()) {
+		(Some("GET"), Some(target)) => Some(target.to_string()),
+		_ => None,
+	})
+}
+
+async fn respond(stream: &mut TcpStream, status: &str, message: &str) -> ApiResult<()> {
+	let message = message
+		.replace('&', "&amp;")
+		.replace('<', "&lt;")
+		.replace('>', "&gt;");
+	let body = format!("<!DOCTYPE html><html><body><p>{message}</p></body></html>");
+	let response = format!(
+		"HTTP/1.1 {status}\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+		body.len()
+	);
+	stream.write_all(response.as_bytes()).await?;
+	stream.shutdown().await?;
+	Ok(())
+}
+
+fn random_string(len: usize) -> String {
+	rand::thread_rng()
+		.sample_iter(&Alphanumeric)
+		.take(len)
+		.map(char::from)
+		.collect()
+}
@@ -0,0 +1,209 @@
+//! Synchronous versions of `OAuth` and `WcifOAuth` for tools that do not use async. Each
+//! `OAuth` owns a small single threaded runtime which the requests are run on, so these types
+//! must not be used from within an async runtime.
+
+use std::future::Future;
+use std::ops::{Deref, DerefMut};
+use std::path::Path;
+use std::time::SystemTime;
+
+use reqwest::Url;
+use tokio::runtime::Runtime;
+
+use crate::{
+	ApiResult, AuthorizationRequest, Competition, CompetitionFilter, FormatVersion, OAuthBuilder,
+	RetryPolicy, Snapshot, SnapshotStore, WcifContainer,
+};
+
+#[derive(Debug)]
+pub struct OAuth {
+	inner: crate::OAuth,
+	runtime: Runtime,
+}
+
+fn runtime() -> ApiResult<Runtime> {
+	Ok(tokio::runtime::Builder::new_current_thread()
+		.enable_all()
+		.build()?)
+}
+
+impl OAuth {
+	/// Wraps an async `OAuth`.
+	pub fn new(inner: crate::OAuth) -> ApiResult<OAuth> {
+		Ok(OAuth {
+			inner,
+			runtime: runtime()?,
+		})
+	}
+
+	fn from_future(future: impl Future<Output = ApiResult<crate::OAuth>>) -> ApiResult<OAuth> {
+		let runtime = runtime()?;
+		let inner = runtime.block_on(future)?;
+		Ok(OAuth { inner, runtime })
+	}
+
+	pub fn get_auth(
+		client_id: String,
+		client_secret: String,
+		redirect_uri: String,
+		auth_code: String,
+	) -> ApiResult<OAuth> {
+		OAuth::from_future(crate::OAuth::get_auth(
+			client_id,
+			client_secret,
+			redirect_uri,
+			auth_code,
+		))
+	}
+
+	/// See `OAuthBuilder::get_auth`.
+	pub fn get_auth_with(builder: OAuthBuilder, auth_code: String) -> ApiResult<OAuth> {
+		OAuth::from_future(builder.get_auth(auth_code))
+	}
+
+	/// See `OAuthBuilder::authorize`.
+	pub fn authorize(
+		builder: OAuthBuilder,
+		request: &AuthorizationRequest,
+		open: impl FnOnce(&Url),
+	) -> ApiResult<OAuth> {
+		OAuth::from_future(builder.authorize(request, open))
+	}
+
+	/// If you use this you need to get a token before hand. See `OAuth::get_auth_implicit`.
+	pub fn get_auth_implicit(
+		client_id: String,
+		access_token: String,
+		redirect_uri: String,
+	) -> ApiResult<OAuth> {
+		OAuth::new(OAuthBuilder::new(client_id, redirect_uri).implicit(access_token))
+	}
+
+	/// See `OAuth::load`.
+	pub fn load(path: impl AsRef<Path>) -> ApiResult<OAuth> {
+		OAuth::from_future(crate::OAuth::load(path))
+	}
+
+	/// See `OAuth::load_private`.
+	pub fn load_private(path: impl AsRef<Path>) -> ApiResult<OAuth> {
+		OAuth::from_future(crate::OAuth::load_private(path))
+	}
+
+	/// See `OAuthBuilder::load`.
+	pub fn load_with(builder: OAuthBuilder, path: impl AsRef<Path>) -> ApiResult<OAuth> {
+		OAuth::from_future(builder.load(path))
+	}
+
+	/// See `OAuthBuilder::load_private`.
+	pub fn load_private_with(builder: OAuthBuilder, path: impl AsRef<Path>) -> ApiResult<OAuth> {
+		OAuth::from_future(builder.load_private(path))
+	}
+
+	pub fn save(&self, path: impl AsRef<Path>) -> ApiResult<()> {
+		self.runtime.block_on(self.inner.save(path))
+	}
+
+	pub fn as_async(&self) -> &crate::OAuth {
+		&self.inner
+	}
+
+	pub fn into_async(self) -> crate::OAuth {
+		self.inner
+	}
+
+	pub fn retry_policy(&self) -> &RetryPolicy {
+		self.inner.retry_policy()
+	}
+
+	pub fn set_retry_policy(&mut self, retry_policy: RetryPolicy) {
+		self.inner.set_retry_policy(retry_policy);
+	}
+
+	pub fn expires_at(&self) -> Option<SystemTime> {
+		self.runtime.block_on(self.inner.expires_at())
+	}
+
+	pub fn refresh_auth(&self) -> ApiResult<()> {
+		self.runtime.block_on(self.inner.refresh_auth())
+	}
+
+	pub fn get_wcif_api(&self, id: &str) -> ApiResult<String> {
+		self.runtime.block_on(self.inner.get_wcif_api(id))
+	}
+
+	pub fn get_wcif_api_version(&self, id: &str, version: &FormatVersion) -> ApiResult<String> {
+		self.runtime
+			.block_on(self.inner.get_wcif_api_version(id, version))
+	}
+
+	pub fn get_wcif(&self, id: &str) -> ApiResult<WcifContainer> {
+		self.runtime.block_on(self.inner.get_wcif(id))
+	}
+
+	pub fn get_competitions_managed_by_me(&self) -> ApiResult<Vec<Competition>> {
+		self.runtime
+			.block_on(self.inner.get_competitions_managed_by_me())
+	}
+
+	pub fn get_competitions_managed_by_me_filtered(
+		&self,
+		filter: &CompetitionFilter,
+	) -> ApiResult<Vec<Competition>> {
+		self.runtime
+			.block_on(self.inner.get_competitions_managed_by_me_filtered(filter))
+	}
+
+	pub(crate) fn block_on<F: Future>(&self, future: F) -> F::Output {
+		self.runtime.block_on(future)
+	}
+}
+
+pub struct WcifOAuth {
+	cont: WcifContainer,
+	oauth: OAuth,
+}
+
+impl Deref for WcifOAuth {
+	type Target = WcifContainer;
+
+	fn deref(&self) -> &Self::Target {
+		&self.cont
+	}
+}
+
+impl DerefMut for WcifOAuth {
+	fn deref_mut(&mut self) -> &mut Self::Target {
+		&mut self.cont
+	}
+}
+
+impl WcifOAuth {
+	pub fn patch(&mut self) -> ApiResult<String> {
+		self.cont.patch_blocking(&self.oauth)
+	}
+
+	pub fn patch_with_reason(&mut self, reason: &str) -> ApiResult<String> {
+		let oauth = &self.oauth;
+		oauth.block_on(self.cont.patch_with_reason(oauth.as_async(), reason))
+	}
+
+	pub fn restore(&mut self, store: &SnapshotStore, snapshot: &Snapshot) -> ApiResult<String> {
+		let oauth = &self.oauth;
+		oauth.block_on(self.cont.restore(store, snapshot, oauth.as_async()))
+	}
+
+	pub fn disassemble(self) -> (WcifContainer, OAuth) {
+		(self.cont, self.oauth)
+	}
+}
+
+impl WcifContainer {
+	pub fn add_blocking_oauth(self, oauth: OAuth) -> WcifOAuth {
+		WcifOAuth { cont: self, oauth }
+	}
+
+	/// Synchronous version of `patch`.
+	pub fn patch_blocking(&mut self, oauth: &OAuth) -> ApiResult<String> {
+		oauth.block_on(self.patch(oauth.as_async()))
+	}
+}
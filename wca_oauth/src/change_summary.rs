@@ -0,0 +1,223 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+use crate::wcif_container::ActivityIter;
+use crate::*;
+
+/// What changed between two versions of a wcif, in terms of groups: activities, assignments and
+/// scramble set counts. Used to preview changes before patching them.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChangeSummary {
+	pub activities_added: Vec<ActivitySummary>,
+	pub activities_removed: Vec<ActivitySummary>,
+	/// Persons who got assignments added or removed.
+	pub assignments: Vec<AssignmentChanges>,
+	pub scramble_set_counts: Vec<ScrambleSetCountChange>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActivitySummary {
+	pub id: usize,
+	pub activity_code: ActivityCode,
+	pub name: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AssignmentChanges {
+	pub wca_user_id: usize,
+	pub registrant_id: Option<usize>,
+	pub name: String,
+	pub added: Vec<AssignmentSummary>,
+	pub removed: Vec<AssignmentSummary>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AssignmentSummary {
+	pub activity_id: usize,
+	/// `None` if the activity does not exist.
+	pub activity_code: Option<ActivityCode>,
+	pub assignment_code: AssignmentCode,
+	pub station_number: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScrambleSetCountChange {
+	pub round_id: String,
+	pub before: usize,
+	pub after: usize,
+}
+
+impl ChangeSummary {
+	pub fn between(before: &Wcif, after: &Wcif) -> ChangeSummary {
+		let before_activities = activities_by_id(before);
+		let after_activities = activities_by_id(after);
+
+		let scramble_set_counts = after
+			.events
+			.iter()
+			.flat_map(|event| &event.rounds)
+			.filter_map(|round| {
+				let previous = before
+					.events
+					.iter()
+					.flat_map(|event| &event.rounds)
+					.find(|r| r.id == round.id)?;
+				(previous.scramble_set_count != round.scramble_set_count).then(|| {
+					ScrambleSetCountChange {
+						round_id: round.id.clone(),
+						before: previous.scramble_set_count,
+						after: round.scramble_set_count,
+					}
+				})
+			})
+			.collect();
+
+		ChangeSummary {
+			activities_added: missing_from(&after_activities, &before_activities),
+			activities_removed: missing_from(&before_activities, &after_activities),
+			assignments: assignment_changes(before, after),
+			scramble_set_counts,
+		}
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.activities_added.is_empty()
+			&& self.activities_removed.is_empty()
+			&& self.assignments.is_empty()
+			&& self.scramble_set_counts.is_empty()
+	}
+}
+
+impl Display for ChangeSummary {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		if self.is_empty() {
+			return writeln!(f, "No changes.");
+		}
+		for activity in &self.activities_added {
+			writeln!(
+				f,
+				"+ activity {} ({})",
+				activity.activity_code, activity.name
+			)?;
+		}
+		for activity in &self.activities_removed {
+			writeln!(
+				f,
+				"- activity {} ({})",
+				activity.activity_code, activity.name
+			)?;
+		}
+		for change in &self.scramble_set_counts {
+			writeln!(
+				f,
+				"~ {} scramble sets: {} -> {}",
+				change.round_id, change.before, change.after
+			)?;
+		}
+		for person in &self.assignments {
+			writeln!(f, "{}:", person.name)?;
+			for assignment in &person.added {
+				writeln!(f, "  + {assignment}")?;
+			}
+			for assignment in &person.removed {
+				writeln!(f, "  - {assignment}")?;
+			}
+		}
+		Ok(())
+	}
+}
+
+impl Display for AssignmentSummary {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		match &self.activity_code {
+			Some(code) => write!(f, "{} in {code}", self.assignment_code)?,
+			None => write!(
+				f,
+				"{} in activity {}",
+				self.assignment_code, self.activity_id
+			)?,
+		}
+		if let Some(station) = self.station_number {
+			write!(f, " at station {station}")?;
+		}
+		Ok(())
+	}
+}
+
+/// The persons of `after` whose assignments differ from how they were in `before`, in the order
+/// of `after`. Persons are matched by their wca user id.
+pub(crate) fn assignment_changes(before: &Wcif, after: &Wcif) -> Vec<AssignmentChanges> {
+	let before_activities = activities_by_id(before);
+	let after_activities = activities_by_id(after);
+	let previous: HashMap<_, _> = before
+		.persons
+		.iter()
+		.map(|person| (person.wca_user_id, person.assignments.as_slice()))
+		.collect();
+	after
+		.persons
+		.iter()
+		.filter_map(|person| {
+			let previous = previous
+				.get(&person.wca_user_id)
+				.copied()
+				.unwrap_or_default();
+			let changes = AssignmentChanges {
+				wca_user_id: person.wca_user_id,
+				registrant_id: person.registrant_id,
+				name: person.name.clone(),
+				added: difference(&person.assignments, previous, &after_activities),
+				removed: difference(previous, &person.assignments, &before_activities),
+			};
+			(!changes.added.is_empty() || !changes.removed.is_empty()).then_some(changes)
+		})
+		.collect()
+}
+
+fn activities_by_id(wcif: &Wcif) -> HashMap<usize, &Activity> {
+	wcif.schedule
+		.venues
+		.iter()
+		.flat_map(|venue| &venue.rooms)
+		.flat_map(|room| ActivityIter::new(&room.activities))
+		.map(|activity| (activity.id, activity))
+		.collect()
+}
+
+/// The activities which are not in `other`, ordered by id.
+fn missing_from(
+	activities: &HashMap<usize, &Activity>,
+	other: &HashMap<usize, &Activity>,
+) -> Vec<ActivitySummary> {
+	let mut missing: Vec<ActivitySummary> = activities
+		.values()
+		.filter(|activity| !other.contains_key(&activity.id))
+		.map(|activity| ActivitySummary {
+			id: activity.id,
+			activity_code: activity.activity_code.clone(),
+			name: activity.name.clone(),
+		})
+		.collect();
+	missing.sort_by_key(|activity| activity.id);
+	missing
+}
+
+/// The assignments which are not in `other`.
+fn difference(
+	assignments: &[Assignment],
+	other: &[Assignment],
+	activities: &HashMap<usize, &Activity>,
+) -> Vec<AssignmentSummary> {
+	assignments
+		.iter()
+		.filter(|assignment| !other.contains(assignment))
+		.map(|assignment| AssignmentSummary {
+			activity_id: assignment.activity_id,
+			activity_code: activities
+				.get(&assignment.activity_id)
+				.map(|activity| activity.activity_code.clone()),
+			assignment_code: assignment.assignment_code.clone(),
+			station_number: assignment.station_number,
+		})
+		.collect()
+}
@@ -0,0 +1,61 @@
+use crate::Date;
+
+/// Filters for listing competitions. Translated to query parameters of the competitions api.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CompetitionFilter {
+	pub ongoing_and_future: Option<Date>,
+	pub start: Option<Date>,
+	pub end: Option<Date>,
+	pub country_iso2: Option<String>,
+	pub include_cancelled: bool,
+}
+
+impl CompetitionFilter {
+	pub fn new() -> CompetitionFilter {
+		CompetitionFilter::default()
+	}
+
+	/// Only competitions that have not ended before `today`.
+	pub fn upcoming(mut self, today: Date) -> Self {
+		self.ongoing_and_future = Some(today);
+		self
+	}
+
+	/// Only competitions taking place between `start` and `end`, both inclusive.
+	pub fn between(mut self, start: Date, end: Date) -> Self {
+		self.start = Some(start);
+		self.end = Some(end);
+		self
+	}
+
+	pub fn country(mut self, country_iso2: impl Into<String>) -> Self {
+		self.country_iso2 = Some(country_iso2.into());
+		self
+	}
+
+	pub fn include_cancelled(mut self) -> Self {
+		self.include_cancelled = true;
+		self
+	}
+
+	#[cfg(feature = "client")]
+	pub(crate) fn query(&self) -> Vec<(&'static str, String)> {
+		let mut query = Vec::new();
+		if let Some(date) = self.ongoing_and_future {
+			query.push(("ongoing_and_future", date.to_string()));
+		}
+		if let Some(date) = self.start {
+			query.push(("start", date.to_string()));
+		}
+		if let Some(date) = self.end {
+			query.push(("end", date.to_string()));
+		}
+		if let Some(country_iso2) = &self.country_iso2 {
+			query.push(("country_iso2", country_iso2.clone()));
+		}
+		if self.include_cancelled {
+			query.push(("include_cancelled", "true".to_string()));
+		}
+		query
+	}
+}
@@ -1,16 +1,44 @@
 use serde::{Deserialize, Serialize};
 
+use crate::{Date, DateTime, EventId};
+
+mod filter;
+mod result;
+mod user;
+
+pub use filter::*;
+pub use result::*;
+pub use user::*;
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Competition {
 	pub id: String,
 	pub name: String,
-	pub registration_open: String,
-	pub registration_close: String,
-	pub announced_at: Option<String>,
-	pub start_date: String,
-	pub end_date: String,
+	#[serde(
+		deserialize_with = "crate::de_date_time",
+		serialize_with = "crate::ser_date_time"
+	)]
+	pub registration_open: DateTime,
+	#[serde(
+		deserialize_with = "crate::de_date_time",
+		serialize_with = "crate::ser_date_time"
+	)]
+	pub registration_close: DateTime,
+	#[serde(
+		default,
+		deserialize_with = "crate::de_opt_date_time",
+		serialize_with = "crate::ser_opt_date_time"
+	)]
+	pub announced_at: Option<DateTime>,
+	pub start_date: Date,
+	pub end_date: Date,
 	pub competitor_limit: Option<u64>,
-	pub cancelled_at: Option<String>,
+	#[serde(
+		default,
+		deserialize_with = "crate::de_opt_date_time",
+		serialize_with = "crate::ser_opt_date_time"
+	)]
+	pub cancelled_at: Option<DateTime>,
 	pub url: Option<String>,
 	pub website: Option<String>,
 	pub short_name: String,
@@ -20,14 +48,14 @@ pub struct Competition {
 	pub latitude_degrees: f64,
 	pub longitude_degrees: f64,
 	pub country_iso2: String,
-	pub event_ids: Vec<String>,
-	pub delegates: Vec<serde_json::Value>,
-	pub organizers: Vec<serde_json::Value>,
+	pub event_ids: Vec<EventId>,
+	pub delegates: Vec<CompetitionUser>,
+	pub organizers: Vec<CompetitionUser>,
 }
 
 impl Competition {
-	pub fn from_json(json: &str) -> Vec<Competition> {
-		serde_json::from_str(json).unwrap()
+	pub fn from_json(json: &str) -> serde_json::Result<Vec<Competition>> {
+		serde_json::from_str(json)
 	}
 
 	pub fn name(&self) -> &str {
@@ -37,4 +65,27 @@ impl Competition {
 	pub fn id(&self) -> &str {
 		&self.id
 	}
+
+	pub fn is_cancelled(&self) -> bool {
+		self.cancelled_at.is_some()
+	}
+
+	pub fn is_announced(&self) -> bool {
+		self.announced_at.is_some()
+	}
+
+	/// True if the competition starts after `today`.
+	pub fn is_upcoming(&self, today: Date) -> bool {
+		today < self.start_date
+	}
+
+	/// True if `today` is one of the days of the competition.
+	pub fn is_ongoing(&self, today: Date) -> bool {
+		self.start_date <= today && today <= self.end_date
+	}
+
+	/// True if registration has opened and not yet closed at `now` (UTC).
+	pub fn registration_is_open(&self, now: DateTime) -> bool {
+		self.registration_open <= now && now < self.registration_close
+	}
 }
@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{EventId, ResultValue, WcaId};
+
+/// A row of the official results of a competition as returned by the results api.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct CompetitionResult {
+	pub id: usize,
+	pub pos: usize,
+	pub best: ResultValue,
+	pub average: ResultValue,
+	pub name: String,
+	pub country_iso2: String,
+	pub competition_id: String,
+	pub event_id: EventId,
+	pub round_type_id: String,
+	pub format_id: String,
+	pub wca_id: WcaId,
+	pub attempts: Vec<ResultValue>,
+	pub regional_single_record: Option<String>,
+	pub regional_average_record: Option<String>,
+}
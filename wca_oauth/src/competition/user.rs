@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{Avatar, WcaId};
+
+/// A delegate or organizer of a competition as returned by the competitions api.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct CompetitionUser {
+	pub id: usize,
+	pub name: String,
+	pub wca_id: Option<WcaId>,
+	pub avatar: Option<Avatar>,
+}
@@ -0,0 +1,80 @@
+use std::collections::HashMap;
+
+use futures::{stream, StreamExt};
+
+use crate::*;
+
+/// The wcifs of several competitions, e.g. every competition of a series or a weekend. A
+/// competition whose wcif could not be fetched keeps its error, so the others are still usable.
+#[derive(Debug)]
+pub struct Dashboard {
+	competitions: Vec<Competition>,
+	wcifs: HashMap<String, ApiResult<WcifContainer>>,
+}
+
+impl Dashboard {
+	/// Fetches the wcif of every competition with at most `parallel` requests at a time.
+	pub async fn fetch(
+		oauth: &OAuth,
+		competitions: Vec<Competition>,
+		parallel: usize,
+	) -> Dashboard {
+		let wcifs = oauth.get_wcifs(&competitions, parallel).await;
+		Dashboard {
+			competitions,
+			wcifs,
+		}
+	}
+
+	/// Fetches the wcifs of every competition managed by the user.
+	pub async fn fetch_managed_by_me(oauth: &OAuth, parallel: usize) -> ApiResult<Dashboard> {
+		let competitions = oauth.get_competitions_managed_by_me().await?;
+		Ok(Dashboard::fetch(oauth, competitions, parallel).await)
+	}
+
+	pub fn competitions(&self) -> &[Competition] {
+		&self.competitions
+	}
+
+	pub fn wcif(&self, id: &str) -> Option<&ApiResult<WcifContainer>> {
+		self.wcifs.get(id)
+	}
+
+	pub fn wcif_mut(&mut self, id: &str) -> Option<&mut ApiResult<WcifContainer>> {
+		self.wcifs.get_mut(id)
+	}
+
+	/// Competitions together with their wcif, in the order the competitions were given.
+	pub fn iter(&self) -> impl Iterator<Item = (&Competition, &ApiResult<WcifContainer>)> {
+		self.competitions
+			.iter()
+			.filter_map(|competition| Some((competition, self.wcifs.get(&competition.id)?)))
+	}
+
+	pub fn failures(&self) -> impl Iterator<Item = (&Competition, &WcaApiError)> {
+		self.iter()
+			.filter_map(|(competition, wcif)| Some((competition, wcif.as_ref().err()?)))
+	}
+
+	pub fn into_wcifs(self) -> HashMap<String, ApiResult<WcifContainer>> {
+		self.wcifs
+	}
+}
+
+impl OAuth {
+	/// Fetches the wcifs of `competitions` concurrently with at most `parallel` requests at a
+	/// time. Every competition gets an entry, failed ones with their error.
+	pub async fn get_wcifs(
+		&self,
+		competitions: &[Competition],
+		parallel: usize,
+	) -> HashMap<String, ApiResult<WcifContainer>> {
+		stream::iter(competitions)
+			.map(|competition| async move {
+				(competition.id.clone(), self.get_wcif(&competition.id).await)
+			})
+			.buffer_unordered(parallel.max(1))
+			.collect()
+			.await
+	}
+}
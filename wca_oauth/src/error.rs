@@ -0,0 +1,99 @@
+use std::fmt::{Display, Formatter};
+
+use reqwest::StatusCode;
+
+use crate::MergeConflict;
+
+pub type ApiResult<T> = std::result::Result<T, WcaApiError>;
+
+/// Error returned by every call that talks to the WCA website.
+#[derive(Debug)]
+pub enum WcaApiError {
+	/// The request could not be sent or the response body could not be read.
+	Transport(reqwest::Error),
+	/// The server answered with a non-success status and a body that is not a WCA error.
+	Http { status: StatusCode, body: String },
+	/// The server answered with a WCA error body, e.g. `{"error": "Not logged in"}`.
+	Wca {
+		status: StatusCode,
+		error: String,
+		description: Option<String>,
+	},
+	/// The response body did not have the expected shape.
+	Decode(serde_json::Error),
+	/// The access token expired and cannot be refreshed, e.g. because it was obtained through the
+	/// implicit flow. The user has to log in again.
+	ReauthenticationRequired,
+	/// The authorization code could not be obtained, e.g. because the user denied access or the
+	/// state of the redirect did not match.
+	Authorization(String),
+	/// A local io operation failed, e.g. listening on the redirect uri.
+	Io(std::io::Error),
+	/// The wcif was changed remotely since it was fetched, and the changes conflict with the
+	/// local ones. Nothing was patched.
+	Conflict(Vec<MergeConflict>),
+}
+
+impl Display for WcaApiError {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		match self {
+			WcaApiError::Transport(e) => write!(f, "request to the WCA failed: {e}"),
+			WcaApiError::Http { status, body } => {
+				write!(f, "the WCA responded with {status}: {body}")
+			}
+			WcaApiError::Wca {
+				status,
+				error,
+				description: Some(description),
+			} => write!(
+				f,
+				"the WCA responded with {status}: {error} ({description})"
+			),
+			WcaApiError::Wca { status, error, .. } => {
+				write!(f, "the WCA responded with {status}: {error}")
+			}
+			WcaApiError::Decode(e) => write!(f, "unable to decode response from the WCA: {e}"),
+			WcaApiError::ReauthenticationRequired => {
+				write!(f, "the access token expired, please authenticate again")
+			}
+			WcaApiError::Authorization(e) => write!(f, "authorization failed: {e}"),
+			WcaApiError::Io(e) => write!(f, "{e}"),
+			WcaApiError::Conflict(conflicts) => {
+				write!(f, "the wcif was changed by someone else:")?;
+				for conflict in conflicts {
+					write!(f, "\n{conflict}")?;
+				}
+				Ok(())
+			}
+		}
+	}
+}
+
+impl std::error::Error for WcaApiError {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self {
+			WcaApiError::Transport(e) => Some(e),
+			WcaApiError::Decode(e) => Some(e),
+			WcaApiError::Io(e) => Some(e),
+			_ => None,
+		}
+	}
+}
+
+impl From<reqwest::Error> for WcaApiError {
+	fn from(e: reqwest::Error) -> Self {
+		WcaApiError::Transport(e)
+	}
+}
+
+impl From<std::io::Error> for WcaApiError {
+	fn from(e: std::io::Error) -> Self {
+		WcaApiError::Io(e)
+	}
+}
+
+impl From<serde_json::Error> for WcaApiError {
+	fn from(e: serde_json::Error) -> Self {
+		WcaApiError::Decode(e)
+	}
+}
@@ -0,0 +1,91 @@
+use std::future::Future;
+
+use futures::{stream, Stream, TryStreamExt};
+use reqwest::{Response, StatusCode};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+
+use crate::{ApiResult, WcaApiError};
+
+#[derive(Deserialize)]
+struct ErrorBody {
+	error: String,
+	error_description: Option<String>,
+}
+
+/// Reads the body of a response. Non-success statuses are turned into errors, using the WCA
+/// error body when there is one.
+pub(crate) async fn read_response(response: Response) -> ApiResult<String> {
+	let status = response.status();
+	let body = response.text().await?;
+	if status.is_success() {
+		return Ok(body);
+	}
+	Err(match serde_json::from_str::<ErrorBody>(&body) {
+		Ok(error) => WcaApiError::Wca {
+			status,
+			error: error.error,
+			description: error.error_description,
+		},
+		Err(_) => WcaApiError::Http { status, body },
+	})
+}
+
+/// The WCA sometimes answers with status 200 and an error body, so decoding falls back to it.
+pub(crate) fn decode<T: DeserializeOwned>(body: &str) -> ApiResult<T> {
+	serde_json::from_str(body).map_err(|e| match serde_json::from_str::<ErrorBody>(body) {
+		Ok(error) => WcaApiError::Wca {
+			status: StatusCode::OK,
+			error: error.error,
+			description: error.error_description,
+		},
+		Err(_) => WcaApiError::Decode(e),
+	})
+}
+
+/// Extracts the url of the next page from a `Link` header as sent by paginated endpoints.
+pub(crate) fn next_page(response: &Response) -> Option<String> {
+	let link = response
+		.headers()
+		.get(reqwest::header::LINK)?
+		.to_str()
+		.ok()?;
+	link.split(',').find_map(|part| {
+		let (url, params) = part.split_once(';')?;
+		params
+			.split(';')
+			.any(|param| param.trim() == "rel=\"next\"")
+			.then(|| {
+				url.trim()
+					.trim_start_matches('<')
+					.trim_end_matches('>')
+					.to_string()
+			})
+	})
+}
+
+/// Streams the items of a paginated endpoint. `send` is called with `None` for the first page
+/// and with the url from the `Link` header for the following pages.
+pub(crate) fn paginate<'a, T, F, Fut>(send: F) -> impl Stream<Item = ApiResult<T>> + 'a
+where
+	T: DeserializeOwned + 'a,
+	F: Fn(Option<String>) -> Fut + 'a,
+	Fut: Future<Output = ApiResult<Response>> + 'a,
+{
+	// `Some(None)` is the first page, `None` means the last page has been fetched.
+	stream::try_unfold((Some(None), send), |(page, send)| async move {
+		let url = match page {
+			Some(url) => url,
+			None => return ApiResult::Ok(None),
+		};
+		let response = send(url).await?;
+		let next = next_page(&response);
+		let items: Vec<T> = decode(&read_response(response).await?)?;
+		let next = next.filter(|_| !items.is_empty()).map(Some);
+		Ok(Some((
+			stream::iter(items.into_iter().map(Ok)),
+			(next, send),
+		)))
+	})
+	.try_flatten()
+}
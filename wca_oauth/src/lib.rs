@@ -1,29 +1,89 @@
+//! Data model of the WCA's competition interchange format (WCIF) and the competitions api, and,
+//! with the default `client` feature, an OAuth client for the WCA api. Without `client` only the
+//! model is compiled, which depends on nothing but serde and chrono and builds for
+//! `wasm32-unknown-unknown`.
+//!
+//! The default `tz` feature adds the local times of activities, e.g. `Activity::local_start`,
+//! using the time zone database of chrono-tz. It embeds the database, which adds a few hundred
+//! kilobytes to a binary, so disable it when that matters, e.g. for wasm. Without it, times are
+//! shown in UTC.
+
+#[cfg(feature = "client")]
+mod authorization;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+mod change_summary;
 mod competition;
+#[cfg(feature = "client")]
+mod dashboard;
+#[cfg(feature = "client")]
+mod error;
+#[cfg(feature = "client")]
+mod http;
+mod merge;
+#[cfg(feature = "client")]
 mod oauth;
+#[cfg(feature = "client")]
+mod oauth_builder;
+#[cfg(feature = "client")]
+mod public_client;
+#[cfg(feature = "client")]
+mod retry;
+mod scheduled_activity;
+mod scramble_sets;
+#[cfg(feature = "client")]
+mod session;
+#[cfg(feature = "client")]
+mod snapshot;
+mod wca_person;
 mod wcif;
+mod wcif_container;
+#[cfg(feature = "client")]
 mod wcif_oauth;
 
+#[cfg(feature = "client")]
+pub use authorization::*;
+pub use change_summary::*;
 pub use competition::*;
+#[cfg(feature = "client")]
+pub use dashboard::*;
+#[cfg(feature = "client")]
+pub use error::*;
+pub use merge::MergeConflict;
+#[cfg(feature = "client")]
 pub use oauth::*;
+#[cfg(feature = "client")]
+pub use oauth_builder::*;
+#[cfg(feature = "client")]
+pub use public_client::*;
+#[cfg(feature = "client")]
+pub use retry::RetryPolicy;
+pub use scheduled_activity::*;
+pub use scramble_sets::{LinkScrambleSetsError, ScrambleSetMismatch};
 use serde::de::Error;
 use serde::{Deserialize, Deserializer, Serializer};
+#[cfg(feature = "client")]
+pub use snapshot::*;
+pub use wca_person::*;
 pub use wcif::*;
+pub use wcif_container::*;
+#[cfg(feature = "client")]
 pub use wcif_oauth::*;
 
-pub use serde_with::chrono::{
-	Datelike, NaiveDate as Date, NaiveDateTime as DateTime, NaiveTime as Time,
-};
+pub use chrono::{Datelike, NaiveDate as Date, NaiveDateTime as DateTime, NaiveTime as Time};
+#[cfg(feature = "tz")]
+pub use chrono_tz::Tz;
+
+/// A time in the time zone of a venue, see `Venue::local_time`.
+#[cfg(feature = "tz")]
+pub type LocalDateTime = chrono::DateTime<Tz>;
 
 fn de_date_time<'de, D>(deserializer: D) -> std::result::Result<DateTime, D::Error>
 where
 	D: Deserializer<'de>,
 {
-	let s: &str = Deserialize::deserialize(deserializer)?;
-	if s.chars().last().unwrap() == 'Z' {
-		Ok(serde_json::from_str(&format!("\"{}\"", &s[0..s.len() - 1])).unwrap())
-	} else {
-		Err(D::Error::custom(s))
-	}
+	let s: String = Deserialize::deserialize(deserializer)?;
+	parse_date_time(&s).ok_or_else(|| D::Error::custom(s))
 }
 
 fn ser_date_time<S>(date_time: &DateTime, serializer: S) -> std::result::Result<S::Ok, S::Error>
@@ -33,3 +93,30 @@ where
 	let str = serde_json::to_string(&date_time).unwrap();
 	serializer.serialize_str(&format!("{}Z", &str[1..str.len() - 1]))
 }
+
+fn de_opt_date_time<'de, D>(deserializer: D) -> std::result::Result<Option<DateTime>, D::Error>
+where
+	D: Deserializer<'de>,
+{
+	let s: Option<String> = Deserialize::deserialize(deserializer)?;
+	s.map(|s| parse_date_time(&s).ok_or_else(|| D::Error::custom(s)))
+		.transpose()
+}
+
+fn ser_opt_date_time<S>(
+	date_time: &Option<DateTime>,
+	serializer: S,
+) -> std::result::Result<S::Ok, S::Error>
+where
+	S: Serializer,
+{
+	match date_time {
+		Some(date_time) => ser_date_time(date_time, serializer),
+		None => serializer.serialize_none(),
+	}
+}
+
+/// Parses an UTC timestamp such as `2023-05-06T09:00:00Z` or `2023-05-06T09:00:00.000Z`.
+fn parse_date_time(s: &str) -> Option<DateTime> {
+	s.strip_suffix('Z')?.parse().ok()
+}
@@ -0,0 +1,351 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt::{Display, Formatter};
+
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+
+use crate::ActivityCode;
+
+/// A change made both locally and remotely which could not be merged.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergeConflict {
+	/// Where in the wcif the conflict is, e.g. `persons/102/assignments/4:competitor`. Elements
+	/// of lists are identified by their wca user id, id or activity id and assignment code. The
+	/// groups of a round and the assignments of a person in a round are identified by the round,
+	/// e.g. `schedule/333-r1/childActivities` and `persons/102/assignments/333-r1`.
+	pub path: String,
+	/// The value when the wcif was fetched. `None` if it did not exist.
+	pub base: Option<Value>,
+	/// The value in the local wcif. `None` if it was removed.
+	pub local: Option<Value>,
+	/// The value in the remote wcif. `None` if it was removed.
+	pub remote: Option<Value>,
+}
+
+impl Display for MergeConflict {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		let show = |value: &Option<Value>| match value {
+			Some(value) => value.to_string(),
+			None => "removed".to_string(),
+		};
+		write!(
+			f,
+			"{} was changed to {} locally and to {} remotely",
+			self.path,
+			show(&self.local),
+			show(&self.remote)
+		)
+	}
+}
+
+/// Three-way merge of the json of two versions of a `T` which both derive from `base`. Objects are
+/// merged key by key, and lists whose elements are identifiable, such as persons, assignments,
+/// activities and extensions, element by element. Anything else changed differently on both sides
+/// is a conflict.
+///
+/// Changes which can be merged might still not give a valid `T` together. In that case the
+/// objects and lists containing changes from both sides are returned as conflicts.
+pub(crate) fn merge_json<T: DeserializeOwned>(
+	base: &Value,
+	local: &Value,
+	remote: &Value,
+) -> Result<T, Vec<MergeConflict>> {
+	let mut merger = Merger::default();
+	let merged = merger
+		.merge(String::new(), Some(base), Some(local), Some(remote))
+		.unwrap_or(Value::Null);
+	if !merger.conflicts.is_empty() {
+		return Err(merger.conflicts);
+	}
+	serde_json::from_value(merged).map_err(|_| {
+		if merger.combined.is_empty() {
+			merger.combined.push(Sides {
+				path: String::new(),
+				base: Some(base),
+				local: Some(local),
+				remote: Some(remote),
+			});
+		}
+		merger.combined.into_iter().map(Sides::conflict).collect()
+	})
+}
+
+/// The three versions of a value at `path`.
+struct Sides<'a> {
+	path: String,
+	base: Option<&'a Value>,
+	local: Option<&'a Value>,
+	remote: Option<&'a Value>,
+}
+
+impl Sides<'_> {
+	fn conflict(self) -> MergeConflict {
+		MergeConflict {
+			path: self.path,
+			base: self.base.cloned(),
+			local: self.local.cloned(),
+			remote: self.remote.cloned(),
+		}
+	}
+}
+
+#[derive(Default)]
+struct Merger<'a> {
+	conflicts: Vec<MergeConflict>,
+	/// The innermost objects and lists which were merged from changes of both sides.
+	combined: Vec<Sides<'a>>,
+}
+
+impl<'a> Merger<'a> {
+	fn merge(
+		&mut self,
+		path: String,
+		base: Option<&'a Value>,
+		local: Option<&'a Value>,
+		remote: Option<&'a Value>,
+	) -> Option<Value> {
+		if local == base || local == remote {
+			return remote.cloned();
+		}
+		if remote == base {
+			return local.cloned();
+		}
+		let combined = self.combined.len();
+		let merged = match (base, local, remote) {
+			(
+				Some(Value::Object(base)),
+				Some(Value::Object(local)),
+				Some(Value::Object(remote)),
+			) => Value::Object(self.merge_objects(&path, base, local, remote)),
+			(Some(Value::Array(base)), Some(Value::Array(local)), Some(Value::Array(remote)))
+				if [base, local, remote]
+					.iter()
+					.all(|list| list.iter().all(|item| key_of(item).is_some())) =>
+			{
+				Value::Array(self.merge_lists(&path, base, local, remote))
+			}
+			_ => {
+				self.conflicts.push(MergeConflict {
+					path,
+					base: base.cloned(),
+					local: local.cloned(),
+					remote: remote.cloned(),
+				});
+				return remote.cloned();
+			}
+		};
+		if self.combined.len() == combined {
+			self.combined.push(Sides {
+				path,
+				base,
+				local,
+				remote,
+			});
+		}
+		Some(merged)
+	}
+
+	fn merge_objects(
+		&mut self,
+		path: &str,
+		base: &'a Map<String, Value>,
+		local: &'a Map<String, Value>,
+		remote: &'a Map<String, Value>,
+	) -> Map<String, Value> {
+		let mut keys: Vec<&String> = remote.keys().collect();
+		keys.extend(local.keys().filter(|key| !remote.contains_key(*key)));
+		keys.into_iter()
+			.filter_map(|key| {
+				self.merge(
+					join(path, key),
+					base.get(key),
+					local.get(key),
+					remote.get(key),
+				)
+				.map(|value| (key.clone(), value))
+			})
+			.collect()
+	}
+
+	/// Merges lists element by element. The order of the remote list is kept, and elements added
+	/// locally are appended.
+	fn merge_lists(
+		&mut self,
+		path: &str,
+		base: &'a [Value],
+		local: &'a [Value],
+		remote: &'a [Value],
+	) -> Vec<Value> {
+		let mut keys: Vec<String> = remote.iter().filter_map(key_of).collect();
+		for key in local.iter().chain(base).filter_map(key_of) {
+			if !keys.contains(&key) {
+				keys.push(key);
+			}
+		}
+		keys.into_iter()
+			.filter_map(|key| {
+				self.merge(
+					join(path, &key),
+					find(base, &key),
+					find(local, &key),
+					find(remote, &key),
+				)
+			})
+			.collect()
+	}
+}
+
+/// Changes to the groups of a round made on both sides, which are conflicts even if the elements
+/// could be merged one by one: two grouping runs would otherwise both be kept, e.g. with a
+/// competitor assigned to a group of each. Conflicts are reported if the child activities of a
+/// round, or the assignments of a person within a round, were changed differently on both sides.
+pub(crate) fn round_conflicts(base: &Value, local: &Value, remote: &Value) -> Vec<MergeConflict> {
+	let mut conflicts = Vec::new();
+	let groups = [base, local, remote].map(groups_by_round);
+	let rounds: BTreeSet<&String> = groups.iter().flat_map(|groups| groups.keys()).collect();
+	for round in rounds {
+		let [base, local, remote] = [0, 1, 2].map(|side| groups[side].get(round));
+		if let Some(conflict) = changed_on_both(
+			format!("schedule/{round}/childActivities"),
+			base,
+			local,
+			remote,
+		) {
+			conflicts.push(conflict);
+		}
+	}
+
+	let activity_rounds = [base, local, remote].map(activity_rounds);
+	let persons = [base, local, remote].map(persons_by_id);
+	for (id, person) in &persons[1] {
+		let (Some(base_person), Some(remote_person)) = (persons[0].get(id), persons[2].get(id))
+		else {
+			continue;
+		};
+		let assignments = [(base_person, 0), (person, 1), (remote_person, 2)]
+			.map(|(person, side)| assignments_by_round(person, &activity_rounds[side]));
+		let rounds: BTreeSet<&String> = assignments.iter().flat_map(|a| a.keys()).collect();
+		for round in rounds {
+			let [base, local, remote] = [0, 1, 2].map(|side| assignments[side].get(round));
+			if let Some(conflict) = changed_on_both(
+				format!("persons/{id}/assignments/{round}"),
+				base,
+				local,
+				remote,
+			) {
+				conflicts.push(conflict);
+			}
+		}
+	}
+	conflicts
+}
+
+fn changed_on_both(
+	path: String,
+	base: Option<&Vec<Value>>,
+	local: Option<&Vec<Value>>,
+	remote: Option<&Vec<Value>>,
+) -> Option<MergeConflict> {
+	(local != base && remote != base && local != remote).then(|| MergeConflict {
+		path,
+		base: base.cloned().map(Value::Array),
+		local: local.cloned().map(Value::Array),
+		remote: remote.cloned().map(Value::Array),
+	})
+}
+
+fn activities(wcif: &Value) -> impl Iterator<Item = &Value> {
+	wcif["schedule"]["venues"]
+		.as_array()
+		.into_iter()
+		.flatten()
+		.flat_map(|venue| venue["rooms"].as_array().into_iter().flatten())
+		.flat_map(|room| room["activities"].as_array().into_iter().flatten())
+}
+
+fn round_id(activity: &Value) -> Option<String> {
+	activity["activityCode"]
+		.as_str()?
+		.parse::<ActivityCode>()
+		.ok()?
+		.round_id()
+}
+
+/// The child activities of every round in the schedule, by round id.
+fn groups_by_round(wcif: &Value) -> BTreeMap<String, Vec<Value>> {
+	let mut groups: BTreeMap<String, Vec<Value>> = BTreeMap::new();
+	for activity in activities(wcif) {
+		if let Some(round) = round_id(activity) {
+			let children = activity["childActivities"].as_array().into_iter().flatten();
+			groups.entry(round).or_default().extend(children.cloned());
+		}
+	}
+	groups
+}
+
+/// The round of every activity in the schedule, including child activities, by activity id.
+fn activity_rounds(wcif: &Value) -> HashMap<String, String> {
+	let mut rounds = HashMap::new();
+	let mut stack: Vec<&Value> = activities(wcif).collect();
+	while let Some(activity) = stack.pop() {
+		if let Some(round) = round_id(activity) {
+			rounds.insert(activity["id"].to_string(), round);
+		}
+		stack.extend(activity["childActivities"].as_array().into_iter().flatten());
+	}
+	rounds
+}
+
+fn persons_by_id(wcif: &Value) -> BTreeMap<String, &Value> {
+	wcif["persons"]
+		.as_array()
+		.into_iter()
+		.flatten()
+		.map(|person| (person["wcaUserId"].to_string(), person))
+		.collect()
+}
+
+/// The assignments of a person to activities of a round, by round id.
+fn assignments_by_round(
+	person: &Value,
+	activity_rounds: &HashMap<String, String>,
+) -> BTreeMap<String, Vec<Value>> {
+	let mut assignments: BTreeMap<String, Vec<Value>> = BTreeMap::new();
+	for assignment in person["assignments"].as_array().into_iter().flatten() {
+		if let Some(round) = activity_rounds.get(&assignment["activityId"].to_string()) {
+			assignments
+				.entry(round.clone())
+				.or_default()
+				.push(assignment.clone());
+		}
+	}
+	assignments
+}
+
+fn find<'a>(list: &'a [Value], key: &str) -> Option<&'a Value> {
+	list.iter()
+		.find(|item| key_of(item).as_deref() == Some(key))
+}
+
+/// Identifies an element of a list across versions of a wcif.
+fn key_of(item: &Value) -> Option<String> {
+	let show = |value: &Value| match value {
+		Value::String(s) => s.clone(),
+		value => value.to_string(),
+	};
+	if let Some(id) = item.get("wcaUserId").or_else(|| item.get("id")) {
+		return Some(show(id));
+	}
+	match (item.get("activityId"), item.get("assignmentCode")) {
+		(Some(activity_id), Some(code)) => Some(format!("{}:{}", show(activity_id), show(code))),
+		_ => None,
+	}
+}
+
+fn join(path: &str, key: &str) -> String {
+	if path.is_empty() {
+		key.to_string()
+	} else {
+		format!("{path}/{key}")
+	}
+}
@@ -1,406 +1,311 @@
 use std::collections::HashMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-use crate::Competition;
+use crate::http::{decode, paginate, read_response};
+use crate::retry::send_with_retry;
 use crate::*;
-use reqwest::Client;
+use futures::lock::Mutex;
+use futures::{Stream, TryStreamExt};
+use reqwest::{Client, RequestBuilder, Response, StatusCode};
 use serde::Deserialize;
 
+/// Tokens are refreshed this long before they expire, so they do not expire mid request.
+const REFRESH_MARGIN: Duration = Duration::from_secs(60);
+
 #[derive(Deserialize)]
 struct AuthResponse {
 	access_token: String,
+	#[serde(default)]
 	refresh_token: String,
+	expires_in: Option<u64>,
+	created_at: Option<u64>,
+}
+
+#[derive(Debug)]
+pub(crate) struct Tokens {
+	pub(crate) access_token: String,
+	pub(crate) refresh_token: String,
+	pub(crate) expires_at: Option<SystemTime>,
+}
+
+impl Tokens {
+	fn update(&mut self, auth_response: AuthResponse) {
+		let created_at = auth_response
+			.created_at
+			.map(|secs| UNIX_EPOCH + Duration::from_secs(secs))
+			.unwrap_or_else(SystemTime::now);
+		self.access_token = auth_response.access_token;
+		if !auth_response.refresh_token.is_empty() {
+			self.refresh_token = auth_response.refresh_token;
+		}
+		self.expires_at = auth_response
+			.expires_in
+			.map(|secs| created_at + Duration::from_secs(secs));
+	}
+
+	fn expires_soon(&self) -> bool {
+		self.expires_at
+			.map(|expires_at| expires_at <= SystemTime::now() + REFRESH_MARGIN)
+			.unwrap_or(false)
+	}
 }
 
 #[derive(Debug)]
 pub struct OAuth {
-	access_token: String,
-	refresh_token: String,
-	client_id: String,
-	client_secret: String,
-	redirect_uri: String,
-	client: Client,
+	pub(crate) tokens: Mutex<Tokens>,
+	pub(crate) client_id: String,
+	pub(crate) client_secret: String,
+	pub(crate) redirect_uri: String,
+	pub(crate) auth_url: String,
+	pub(crate) api_url: String,
+	pub(crate) client: Client,
+	pub(crate) retry_policy: RetryPolicy,
+	pub(crate) snapshot_store: Option<SnapshotStore>,
 }
 
 impl OAuth {
+	pub fn builder(client_id: String, redirect_uri: String) -> OAuthBuilder {
+		OAuthBuilder::new(client_id, redirect_uri)
+	}
+
 	pub async fn get_auth(
 		client_id: String,
 		client_secret: String,
 		redirect_uri: String,
 		auth_code: String,
-	) -> Self {
-		let mut oauth = Self {
-			access_token: format!(""),
-			refresh_token: format!(""),
-			client_id,
-			client_secret,
-			redirect_uri,
-			client: reqwest::Client::new(),
-		};
-		oauth.get_auth_explicit_flow(auth_code).await;
-		oauth
+	) -> ApiResult<Self> {
+		OAuthBuilder::new(client_id, redirect_uri)
+			.client_secret(client_secret)
+			.get_auth(auth_code)
+			.await
 	}
 
-	/// If you use this you need to get a token before hand. Refresh cannot be done with this type and will crash.
+	/// If you use this you need to get a token before hand. Refresh cannot be done with this type
+	/// and requests will fail with `WcaApiError::ReauthenticationRequired` once the token expires.
 	pub async fn get_auth_implicit(
 		client_id: String,
 		access_token: String,
 		redirect_uri: String,
 	) -> Self {
-		Self {
-			access_token,
-			refresh_token: String::new(),
-			client_id,
-			client_secret: String::new(),
-			redirect_uri,
-			client: reqwest::Client::new(),
-		}
+		OAuthBuilder::new(client_id, redirect_uri).implicit(access_token)
 	}
 
-	async fn get_auth_explicit_flow(&mut self, code: String) {
-		let mut params = HashMap::new();
+	pub fn auth_url(&self) -> &str {
+		&self.auth_url
+	}
 
-		params.insert("grant_type", "authorization_code");
-		params.insert("client_id", &self.client_id);
-		params.insert("client_secret", &self.client_secret);
-		params.insert("redirect_uri", &self.redirect_uri);
-		params.insert("code", code.trim());
+	pub fn api_url(&self) -> &str {
+		&self.api_url
+	}
 
-		//Request token
-		let response = self
-			.client
-			.post("https://www.worldcubeassociation.org/oauth/token")
-			.form(&params)
-			.send()
-			.await
-			.unwrap()
-			.text()
-			.await
-			.unwrap();
+	pub fn retry_policy(&self) -> &RetryPolicy {
+		&self.retry_policy
+	}
 
-		let auth_response: AuthResponse = serde_json::from_str(&response).unwrap();
+	pub fn set_retry_policy(&mut self, retry_policy: RetryPolicy) {
+		self.retry_policy = retry_policy;
+	}
 
-		self.access_token = auth_response.access_token;
-		self.refresh_token = auth_response.refresh_token;
+	/// Where the remote wcif is saved before every patch, if anywhere.
+	pub fn snapshot_store(&self) -> Option<&SnapshotStore> {
+		self.snapshot_store.as_ref()
 	}
 
-	pub async fn refresh_auth(&mut self) {
+	pub fn set_snapshot_store(&mut self, snapshot_store: Option<SnapshotStore>) {
+		self.snapshot_store = snapshot_store;
+	}
+
+	/// The point in time the current access token expires, if the WCA told us.
+	pub async fn expires_at(&self) -> Option<SystemTime> {
+		self.tokens.lock().await.expires_at
+	}
+
+	pub(crate) async fn get_auth_explicit_flow(
+		&mut self,
+		code: String,
+		code_verifier: Option<&str>,
+	) -> ApiResult<()> {
 		let mut params = HashMap::new();
 
-		params.insert("grant_type", "refresh_token");
+		params.insert("grant_type", "authorization_code");
 		params.insert("client_id", &self.client_id);
-		params.insert("client_secret", &self.client_secret);
-		params.insert("refresh_token", self.refresh_token.trim());
+		if !self.client_secret.is_empty() {
+			params.insert("client_secret", &self.client_secret);
+		}
+		params.insert("redirect_uri", &self.redirect_uri);
+		params.insert("code", code.trim());
+		if let Some(code_verifier) = code_verifier {
+			params.insert("code_verifier", code_verifier);
+		}
 
 		//Request token
 		let response = self
 			.client
-			.post("https://www.worldcubeassociation.org/oauth/token")
+			.post(format!("{}/oauth/token", self.auth_url))
 			.form(&params)
 			.send()
-			.await
-			.unwrap()
-			.text()
-			.await
-			.unwrap();
-
-		let auth_response: AuthResponse = serde_json::from_str(&response).unwrap();
-
-		self.access_token = auth_response.access_token;
-		self.refresh_token = auth_response.refresh_token;
-	}
+			.await?;
 
-	pub async fn get_wcif_api(&self, id: &str) -> String {
-		let get_url = format!(
-			"https://api.worldcubeassociation.org/competitions/{}/wcif/version/2.1.1",
-			id
-		);
-		//Request wcif
-		let response = self
-			.client
-			.get(&get_url)
-			.header("Authorization", format!("Bearer {}", self.access_token))
-			.send()
-			.await
-			.unwrap()
-			.text()
-			.await
-			.unwrap();
+		let auth_response: AuthResponse = decode(&read_response(response).await?)?;
 
-		response
+		self.tokens.get_mut().update(auth_response);
+		Ok(())
 	}
 
-	pub async fn get_wcif(&self, id: &str) -> WcifResult {
-		let json = self.get_wcif_api(id).await;
-		parse(json)
+	pub async fn refresh_auth(&self) -> ApiResult<()> {
+		let mut tokens = self.tokens.lock().await;
+		self.refresh_tokens(&mut tokens).await
 	}
 
-	pub async fn get_competitions_managed_by_me(&self) -> Vec<Competition> {
-		let url = "https://api.worldcubeassociation.org/competitions?managed_by_me=true";
-
-		let json = self
-			.client
-			.get(url)
-			.header("Authorization", format!("Bearer {}", self.access_token))
-			.send()
-			.await
-			.unwrap()
-			.text()
-			.await
-			.unwrap();
-
-		Competition::from_json(&json)
-	}
+	async fn refresh_tokens(&self, tokens: &mut Tokens) -> ApiResult<()> {
+		if tokens.refresh_token.is_empty() {
+			return Err(WcaApiError::ReauthenticationRequired);
+		}
 
-	async fn patch_wcif(&self, wcif: &Wcif, id: &str) -> String {
-		let patch_url = format!(
-			"https://api.worldcubeassociation.org/competitions/{}/wcif",
-			id
-		);
+		let mut params = HashMap::new();
 
-		let json = serde_json::to_string(wcif).unwrap();
+		params.insert("grant_type", "refresh_token");
+		params.insert("client_id", &self.client_id);
+		if !self.client_secret.is_empty() {
+			params.insert("client_secret", &self.client_secret);
+		}
+		params.insert("refresh_token", tokens.refresh_token.trim());
 
+		//Request token
 		let response = self
 			.client
-			.patch(&patch_url)
-			.header("Authorization", format!("Bearer {}", self.access_token))
-			.header("Content-Type", "application/json")
-			.body(json)
+			.post(format!("{}/oauth/token", self.auth_url))
+			.form(&params)
 			.send()
-			.await
-			.unwrap()
-			.text()
-			.await
-			.unwrap();
-
-		response
-	}
-}
-
-#[derive(Debug)]
-pub struct WcifContainer {
-	pub(crate) wcif: Wcif,
-}
-
-impl WcifContainer {
-	pub fn new(wcif: Wcif) -> WcifContainer {
-		WcifContainer { wcif }
-	}
+			.await?;
 
-	pub fn add_oauth(self, oauth: OAuth) -> WcifOAuth {
-		WcifOAuth { cont: self, oauth }
-	}
+		let auth_response: AuthResponse = decode(&read_response(response).await?)?;
 
-	pub fn get_mut<'a>(&'a mut self) -> &'a mut Wcif {
-		&mut self.wcif
+		tokens.update(auth_response);
+		Ok(())
 	}
 
-	pub fn get<'a>(&'a self) -> &'a Wcif {
-		&self.wcif
-	}
-
-	pub async fn patch(&self, oauth: &OAuth) -> String {
-		oauth.patch_wcif(&self.wcif, &self.wcif.id).await
-	}
-
-	pub fn date(&self) -> Date {
-		self.wcif.schedule.start_date
-	}
-
-	pub fn events_iter(&self) -> impl Iterator<Item = &Event> {
-		self.wcif.events.iter()
-	}
-
-	pub fn events_iter_mut(&mut self) -> impl Iterator<Item = &mut Event> {
-		self.wcif.events.iter_mut()
-	}
-
-	pub fn patch_events(&mut self, func: impl FnMut(&mut Event)) {
-		self.events_iter_mut().for_each(func);
-	}
-
-	pub fn persons_iter(&self) -> impl Iterator<Item = &Person> {
-		self.wcif.persons.iter()
-	}
-
-	pub fn persons_iter_mut(&mut self) -> impl Iterator<Item = &mut Person> {
-		self.wcif.persons.iter_mut()
-	}
-
-	pub fn patch_persons(&mut self, func: impl FnMut(&mut Person)) {
-		self.persons_iter_mut().for_each(func);
-	}
+	/// Returns a valid access token, refreshing it first if it is about to expire.
+	pub(crate) async fn access_token(&self) -> ApiResult<String> {
+		let mut tokens = self.tokens.lock().await;
+		if tokens.expires_soon() {
+			self.refresh_tokens(&mut tokens).await?;
+		}
+		Ok(tokens.access_token.clone())
+	}
+
+	/// Sends the request built by `request` with the access token. If the WCA rejects the token,
+	/// it is refreshed and the request is sent once more.
+	pub(crate) async fn send_authorized(
+		&self,
+		request: impl Fn() -> RequestBuilder,
+	) -> ApiResult<String> {
+		read_response(self.send_authorized_response(request).await?).await
+	}
+
+	/// Same as `send_authorized`, but returns the response without looking at its status.
+	pub(crate) async fn send_authorized_response(
+		&self,
+		request: impl Fn() -> RequestBuilder,
+	) -> ApiResult<Response> {
+		let access_token = self.access_token().await?;
+		let response =
+			send_with_retry(|| request().bearer_auth(&access_token), &self.retry_policy).await?;
+		if response.status() != StatusCode::UNAUTHORIZED {
+			return Ok(response);
+		}
 
-	pub fn round_iter(&self) -> impl Iterator<Item = &Round> {
-		self.events_iter().flat_map(|e| e.rounds.iter())
+		let access_token = {
+			let mut tokens = self.tokens.lock().await;
+			// Another request might have refreshed the token while this one was in flight.
+			if tokens.access_token == access_token {
+				self.refresh_tokens(&mut tokens).await?;
+			}
+			tokens.access_token.clone()
+		};
+		send_with_retry(|| request().bearer_auth(&access_token), &self.retry_policy).await
 	}
 
-	pub fn round_iter_mut(&mut self) -> impl Iterator<Item = &mut Round> {
-		self.events_iter_mut().flat_map(|e| e.rounds.iter_mut())
+	pub async fn get_wcif_api(&self, id: &str) -> ApiResult<String> {
+		self.get_wcif_api_version(id, &FormatVersion::LATEST).await
 	}
 
-	pub fn patch_rounds(&mut self, func: impl FnMut(&mut Round)) {
-		self.round_iter_mut().for_each(func);
+	/// The wcif as a document of the given version. `get_wcif` requests the latest version, but
+	/// accepts older ones, upgrading them.
+	pub async fn get_wcif_api_version(
+		&self,
+		id: &str,
+		version: &FormatVersion,
+	) -> ApiResult<String> {
+		let get_url = format!(
+			"{}/competitions/{}/wcif/version/{}",
+			self.api_url, id, version
+		);
+		//Request wcif
+		self.send_authorized(|| self.client.get(&get_url)).await
 	}
 
-	pub fn activity_iter(&self) -> impl Iterator<Item = &Activity> {
-		self.wcif.schedule.venues.iter().flat_map(|venue| {
-			venue
-				.rooms
-				.iter()
-				.flat_map(|room| ActivityIter::new(&room.activities))
+	pub async fn get_wcif(&self, id: &str) -> ApiResult<WcifContainer> {
+		let json = self.get_wcif_api(id).await?;
+		decode(&json).map(|mut wcif: Wcif| {
+			wcif.upgrade();
+			WcifContainer::new(wcif)
 		})
 	}
 
-	pub fn schedule_activity_iter(&self) -> impl Iterator<Item = &Activity> {
-		self.wcif
-			.schedule
-			.venues
-			.iter()
-			.flat_map(|venue| venue.rooms.iter().flat_map(|room| room.activities.iter()))
-	}
-
-	pub fn reg_ids_of_delegates(&self) -> Vec<usize> {
-		self.persons_iter()
-			.filter(|person| {
-				person.roles.contains(&Role::Delegate)
-					|| person.roles.contains(&Role::TraineeDelegate)
-			})
-			.filter_map(|person| person.registrant_id)
-			.collect()
+	/// Returns every competition managed by the user, following all pages.
+	pub async fn get_competitions_managed_by_me(&self) -> ApiResult<Vec<Competition>> {
+		self.get_competitions_managed_by_me_filtered(&CompetitionFilter::default())
+			.await
 	}
 
-	pub fn overlapping_activities<'a>(&'a self) -> Vec<(&'a Activity, &'a Activity)> {
-		self.schedule_activity_iter()
-			.map(|act_1| {
-				self.schedule_activity_iter()
-					.filter(|act_2| act_1.overlaps(act_2))
-					.zip(std::iter::repeat(act_1))
-			})
-			.flatten()
-			.collect()
+	pub async fn get_competitions_managed_by_me_filtered(
+		&self,
+		filter: &CompetitionFilter,
+	) -> ApiResult<Vec<Competition>> {
+		self.competitions_managed_by_me_stream(filter)
+			.try_collect()
+			.await
 	}
 
-	pub fn add_groups_to_event(
-		&mut self,
-		event: &str,
-		round: usize,
-		no: usize,
-		subgroups: usize,
-	) -> std::result::Result<&mut Vec<Activity>, ()> {
-		let act = self
-			.wcif
-			.schedule
-			.venues
-			.iter_mut()
-			.flat_map(|v| &mut v.rooms)
-			.flat_map(|r| &mut r.activities)
-			.find(|a| a.activity_code.contains(&format!("{event}-r{round}")))
-			.map(|a| {
-				if a.child_activities.len() != 0 {
-					let ids: Vec<_> = a.child_activities.iter().map(|a| a.id).collect();
-					for person in self.wcif.persons.iter_mut() {
-						person
-							.assignments
-							.retain(|act| !ids.contains(&act.activity_id));
+	/// Streams the competitions managed by the user, fetching the next page when the current one
+	/// is used up.
+	pub fn competitions_managed_by_me_stream<'a>(
+		&'a self,
+		filter: &CompetitionFilter,
+	) -> impl Stream<Item = ApiResult<Competition>> + 'a {
+		let mut query = filter.query();
+		query.push(("managed_by_me", "true".to_string()));
+		let url = format!("{}/competitions", self.api_url);
+
+		paginate(move |page| {
+			let (url, query) = (url.clone(), query.clone());
+			async move {
+				match page {
+					Some(page) => {
+						self.send_authorized_response(|| self.client.get(&page))
+							.await
+					}
+					None => {
+						self.send_authorized_response(|| self.client.get(&url).query(&query))
+							.await
 					}
 				}
-				a.child_activities = (0..no * subgroups)
-					.map(|g| {
-						let group_time = (a.end_time - a.start_time) / no as i32;
-						let start_time = a.start_time + (group_time * (g / subgroups) as i32);
-						let end_time = a.start_time + (group_time * ((g / subgroups) as i32 + 1));
-						Activity {
-							id: a.id * 1000 + g,
-							name: format!("{}, Group {}", a.name, g + 1),
-							activity_code: format!("{}-g{}", a.activity_code, g + 1),
-							start_time,
-							end_time,
-							child_activities: vec![],
-							// scramble_set_id: None,
-							extensions: vec![],
-						}
-					})
-					.collect();
-				a
-			});
-		match act {
-			Some(v) => Ok(&mut v.child_activities),
-			_ => Err(()),
-		}
-	}
-
-	/// Returns true if there exists groups for the round. Will panic if the event-round pair does not exist.
-	pub fn detect_round_groups_exist(&self, event: &str, round: usize) -> bool {
-		let act = self
-			.wcif
-			.schedule
-			.venues
-			.iter()
-			.flat_map(|v| &v.rooms)
-			.flat_map(|r| &r.activities)
-			.find(|a| a.activity_code.contains(&format!("{event}-r{round}")))
-			.map(|a| !a.child_activities.is_empty())
-			.expect("check that your event and round number is correct");
-		act
-	}
-
-	/// Returns the number of entered competitors and total number of competitors for a given
-	/// round. Will panic if the event-round pair does not exist.
-	pub fn count_entered(&self, event_id: &str, round_no: usize) -> (u64, u64) {
-		let act = self
-			.wcif
-			.events
-			.iter()
-			.find(|event| event.id == event_id)
-			.and_then(|event| {
-				event
-					.rounds
-					.iter()
-					.find(|round| round.id == format!("{event_id}-r{round_no}"))
-			})
-			.map(|round| {
-				let entered = round
-					.results
-					.iter()
-					.filter(|res| res.ranking.is_some())
-					.count();
-				let competitors = round.results.len();
-				(entered as u64, competitors as u64)
-			})
-			.expect("check that your event and round number is correct");
-		act
+			}
+		})
 	}
-}
-
-struct ActivityIter<'a> {
-	activites: Vec<Box<dyn Iterator<Item = &'a Activity> + 'a>>,
-}
 
-impl<'a> ActivityIter<'a> {
-	fn new(vec: &'a Vec<Activity>) -> Self {
-		ActivityIter {
-			activites: vec![Box::new(vec.iter())],
-		}
-	}
-}
+	pub(crate) async fn patch_wcif(&self, wcif: &serde_json::Value, id: &str) -> ApiResult<String> {
+		let patch_url = format!("{}/competitions/{}/wcif", self.api_url, id);
 
-impl<'a> Iterator for ActivityIter<'a> {
-	type Item = &'a Activity;
+		let json = serde_json::to_string(wcif)?;
 
-	fn next(&mut self) -> Option<Self::Item> {
-		if let Some(iter) = self.activites.last_mut() {
-			match iter.next() {
-				None => {
-					self.activites.pop();
-					self.next()
-				}
-				Some(v) => {
-					self.activites.push(Box::new(v.child_activities.iter()));
-					Some(v)
-				}
-			}
-		} else {
-			None
-		}
+		self.send_authorized(|| {
+			self.client
+				.patch(&patch_url)
+				.header("Content-Type", "application/json")
+				.body(json.clone())
+		})
+		.await
 	}
 }
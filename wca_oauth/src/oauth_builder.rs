@@ -0,0 +1,138 @@
+use futures::lock::Mutex;
+use reqwest::{Client, Url};
+
+use crate::{ApiResult, AuthorizationRequest, OAuth, RetryPolicy, SnapshotStore, Tokens};
+
+pub const WCA_URL: &str = "https://www.worldcubeassociation.org";
+pub const WCA_API_URL: &str = "https://api.worldcubeassociation.org";
+
+/// Builder for `OAuth`. By default the production WCA website is used for both authorization and
+/// the API. For the staging site use `https://staging.worldcubeassociation.org` as auth url and
+/// `https://staging.worldcubeassociation.org/api/v0` as api url.
+#[derive(Debug, Clone)]
+pub struct OAuthBuilder {
+	client_id: String,
+	client_secret: String,
+	redirect_uri: String,
+	auth_url: String,
+	api_url: String,
+	client: Option<Client>,
+	retry_policy: RetryPolicy,
+	snapshot_store: Option<SnapshotStore>,
+}
+
+impl OAuthBuilder {
+	pub fn new(client_id: String, redirect_uri: String) -> OAuthBuilder {
+		OAuthBuilder {
+			client_id,
+			client_secret: String::new(),
+			redirect_uri,
+			auth_url: WCA_URL.to_string(),
+			api_url: WCA_API_URL.to_string(),
+			client: None,
+			retry_policy: RetryPolicy::default(),
+			snapshot_store: None,
+		}
+	}
+
+	pub fn client_secret(mut self, client_secret: String) -> Self {
+		self.client_secret = client_secret;
+		self
+	}
+
+	/// Base url of the server handing out tokens. `/oauth/token` is appended to it.
+	pub fn auth_url(mut self, auth_url: impl Into<String>) -> Self {
+		self.auth_url = trim_url(auth_url.into());
+		self
+	}
+
+	/// Base url of the api. Paths such as `/competitions/{id}/wcif` are appended to it.
+	pub fn api_url(mut self, api_url: impl Into<String>) -> Self {
+		self.api_url = trim_url(api_url.into());
+		self
+	}
+
+	/// Use a preconfigured http client, e.g. with custom timeouts.
+	pub fn client(mut self, client: Client) -> Self {
+		self.client = Some(client);
+		self
+	}
+
+	/// How failed requests to the api are retried. Defaults to `RetryPolicy::default()`.
+	pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+		self.retry_policy = retry_policy;
+		self
+	}
+
+	/// Save the remote wcif to `snapshot_store` before every patch.
+	pub fn snapshot_store(mut self, snapshot_store: SnapshotStore) -> Self {
+		self.snapshot_store = Some(snapshot_store);
+		self
+	}
+
+	/// Exchanges the authorization code for tokens using the explicit flow.
+	pub async fn get_auth(self, auth_code: String) -> ApiResult<OAuth> {
+		let mut oauth = self.build(String::new());
+		oauth.get_auth_explicit_flow(auth_code, None).await?;
+		Ok(oauth)
+	}
+
+	/// Same as `get_auth` for a code obtained with a PKCE protected `AuthorizationRequest`.
+	pub async fn get_auth_pkce(self, auth_code: String, code_verifier: &str) -> ApiResult<OAuth> {
+		let mut oauth = self.build(String::new());
+		oauth
+			.get_auth_explicit_flow(auth_code, Some(code_verifier))
+			.await?;
+		Ok(oauth)
+	}
+
+	/// An authorization request for this client, with the same redirect uri and auth url.
+	pub fn authorization_request(&self) -> AuthorizationRequest {
+		AuthorizationRequest::new(self.client_id.clone(), self.redirect_uri.clone())
+			.auth_url(self.auth_url.clone())
+	}
+
+	/// Runs the whole explicit flow. Starts listening on the redirect uri, hands the url the user
+	/// has to visit to `open`, e.g. to open it in a browser, and exchanges the code it receives.
+	pub async fn authorize(
+		self,
+		request: &AuthorizationRequest,
+		open: impl FnOnce(&Url),
+	) -> ApiResult<OAuth> {
+		let listener = request.listen().await?;
+		open(&request.url()?);
+		let auth_code = listener.wait_for_code().await?;
+		match request.code_verifier() {
+			Some(code_verifier) => self.get_auth_pkce(auth_code, code_verifier).await,
+			None => self.get_auth(auth_code).await,
+		}
+	}
+
+	/// Uses an access token obtained through the implicit flow. Refresh cannot be done with this
+	/// and will fail with `WcaApiError::ReauthenticationRequired`.
+	pub fn implicit(self, access_token: String) -> OAuth {
+		self.build(access_token)
+	}
+
+	pub(crate) fn build(self, access_token: String) -> OAuth {
+		OAuth {
+			tokens: Mutex::new(Tokens {
+				access_token,
+				refresh_token: String::new(),
+				expires_at: None,
+			}),
+			client_id: self.client_id,
+			client_secret: self.client_secret,
+			redirect_uri: self.redirect_uri,
+			auth_url: self.auth_url,
+			api_url: self.api_url,
+			client: self.client.unwrap_or_default(),
+			retry_policy: self.retry_policy,
+			snapshot_store: self.snapshot_store,
+		}
+	}
+}
+
+fn trim_url(url: String) -> String {
+	url.trim_end_matches('/').to_string()
+}
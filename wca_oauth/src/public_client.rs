@@ -0,0 +1,120 @@
+use futures::{Stream, TryStreamExt};
+use reqwest::Client;
+
+use crate::http::{decode, paginate, read_response};
+use crate::retry::send_with_retry;
+use crate::*;
+
+/// Client for the parts of the WCA api that do not require authentication.
+#[derive(Debug, Clone)]
+pub struct PublicClient {
+	api_url: String,
+	client: Client,
+	retry_policy: RetryPolicy,
+}
+
+impl Default for PublicClient {
+	fn default() -> Self {
+		PublicClient::new()
+	}
+}
+
+impl PublicClient {
+	pub fn new() -> PublicClient {
+		PublicClient::with_api_url(WCA_API_URL)
+	}
+
+	/// Base url of the api, see `OAuthBuilder::api_url`.
+	pub fn with_api_url(api_url: impl Into<String>) -> PublicClient {
+		PublicClient {
+			api_url: api_url.into().trim_end_matches('/').to_string(),
+			client: Client::new(),
+			retry_policy: RetryPolicy::default(),
+		}
+	}
+
+	/// Use a preconfigured http client, e.g. with custom timeouts.
+	pub fn client(mut self, client: Client) -> Self {
+		self.client = client;
+		self
+	}
+
+	/// How failed requests are retried. Defaults to `RetryPolicy::default()`.
+	pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+		self.retry_policy = retry_policy;
+		self
+	}
+
+	pub fn api_url(&self) -> &str {
+		&self.api_url
+	}
+
+	async fn get(&self, path: &str) -> ApiResult<String> {
+		let url = format!("{}{}", self.api_url, path);
+		let response = send_with_retry(|| self.client.get(&url), &self.retry_policy).await?;
+		read_response(response).await
+	}
+
+	/// The public wcif of a competition. Personal information such as emails and birthdates is
+	/// not included.
+	pub async fn get_wcif_api(&self, id: &str) -> ApiResult<String> {
+		self.get(&format!("/competitions/{}/wcif/public", id)).await
+	}
+
+	pub async fn get_wcif(&self, id: &str) -> ApiResult<WcifContainer> {
+		let json = self.get_wcif_api(id).await?;
+		decode(&json).map(|mut wcif: Wcif| {
+			wcif.upgrade();
+			WcifContainer::new(wcif)
+		})
+	}
+
+	pub async fn get_competition(&self, id: &str) -> ApiResult<Competition> {
+		decode(&self.get(&format!("/competitions/{}", id)).await?)
+	}
+
+	/// Searches competitions by name, city or id, following all pages.
+	pub async fn search_competitions(
+		&self,
+		query: &str,
+		filter: &CompetitionFilter,
+	) -> ApiResult<Vec<Competition>> {
+		self.search_competitions_stream(query, filter)
+			.try_collect()
+			.await
+	}
+
+	pub fn search_competitions_stream<'a>(
+		&'a self,
+		query: &str,
+		filter: &CompetitionFilter,
+	) -> impl Stream<Item = ApiResult<Competition>> + 'a {
+		let mut query_params = filter.query();
+		query_params.push(("q", query.to_string()));
+		let url = format!("{}/competitions", self.api_url);
+
+		paginate(move |page| {
+			let (url, query_params) = (url.clone(), query_params.clone());
+			async move {
+				match page {
+					Some(page) => {
+						send_with_retry(|| self.client.get(&page), &self.retry_policy).await
+					}
+					None => {
+						let request = || self.client.get(&url).query(&query_params);
+						send_with_retry(request, &self.retry_policy).await
+					}
+				}
+			}
+		})
+	}
+
+	pub async fn get_person(&self, wca_id: &str) -> ApiResult<WcaPerson> {
+		decode(&self.get(&format!("/persons/{}", wca_id)).await?)
+	}
+
+	/// The official results of a competition. Empty until the results are posted.
+	pub async fn get_competition_results(&self, id: &str) -> ApiResult<Vec<CompetitionResult>> {
+		decode(&self.get(&format!("/competitions/{}/results", id)).await?)
+	}
+}
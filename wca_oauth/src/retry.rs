@@ -0,0 +1,131 @@
+use std::time::Duration;
+
+use rand::Rng;
+use reqwest::header::RETRY_AFTER;
+use reqwest::{Method, RequestBuilder, Response, StatusCode};
+
+use crate::ApiResult;
+
+/// When and how often a failed request is retried. Requests are retried on transport errors,
+/// `429 Too Many Requests` and `5xx` responses. GET requests are always retried, PATCH requests
+/// only with `retry_patch`, other requests never.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+	/// Total number of attempts including the first one.
+	pub max_attempts: u32,
+	/// Wait before the first retry. Doubled for every following retry.
+	pub initial_backoff: Duration,
+	/// Upper bound of every wait, including waits asked for with `Retry-After`.
+	pub max_backoff: Duration,
+	/// Wait a random duration between half and all of the backoff.
+	pub jitter: bool,
+	pub retry_patch: bool,
+}
+
+impl Default for RetryPolicy {
+	fn default() -> Self {
+		RetryPolicy {
+			max_attempts: 3,
+			initial_backoff: Duration::from_millis(500),
+			max_backoff: Duration::from_secs(30),
+			jitter: true,
+			retry_patch: false,
+		}
+	}
+}
+
+impl RetryPolicy {
+	/// Sends every request exactly once.
+	pub fn none() -> RetryPolicy {
+		RetryPolicy {
+			max_attempts: 1,
+			..RetryPolicy::default()
+		}
+	}
+
+	pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+		self.max_attempts = max_attempts;
+		self
+	}
+
+	pub fn backoff(mut self, initial_backoff: Duration, max_backoff: Duration) -> Self {
+		self.initial_backoff = initial_backoff;
+		self.max_backoff = max_backoff;
+		self
+	}
+
+	pub fn jitter(mut self, jitter: bool) -> Self {
+		self.jitter = jitter;
+		self
+	}
+
+	/// Also retry PATCH requests. Only safe if patching the same data twice is harmless, which is
+	/// the case for wcif patches.
+	pub fn retry_patch(mut self, retry_patch: bool) -> Self {
+		self.retry_patch = retry_patch;
+		self
+	}
+
+	fn allows(&self, request: &RequestBuilder) -> bool {
+		let method = request
+			.try_clone()
+			.and_then(|request| request.build().ok())
+			.map(|request| request.method().clone());
+		match method {
+			Some(Method::GET) | Some(Method::HEAD) => true,
+			Some(Method::PATCH) => self.retry_patch,
+			_ => false,
+		}
+	}
+
+	/// The wait before retry number `retry`, counting from 0. A server asking for a longer wait
+	/// with `Retry-After` is only waited for up to `max_backoff`.
+	pub fn backoff_for(&self, retry: u32) -> Duration {
+		let backoff = self
+			.initial_backoff
+			.saturating_mul(2u32.saturating_pow(retry))
+			.min(self.max_backoff);
+		if self.jitter {
+			backoff.mul_f64(rand::thread_rng().gen_range(0.5..=1.0))
+		} else {
+			backoff
+		}
+	}
+}
+
+/// Sends the request built by `request`, retrying according to `policy`.
+pub(crate) async fn send_with_retry(
+	request: impl Fn() -> RequestBuilder,
+	policy: &RetryPolicy,
+) -> ApiResult<Response> {
+	let retryable = policy.allows(&request());
+	let mut retry = 0;
+	loop {
+		let result = request().send().await;
+		if !retryable || retry + 1 >= policy.max_attempts {
+			return Ok(result?);
+		}
+		let delay = match &result {
+			Ok(response) if should_retry(response.status()) => match retry_after(response) {
+				Some(retry_after) => retry_after.min(policy.max_backoff),
+				None => policy.backoff_for(retry),
+			},
+			Err(e) if e.is_connect() || e.is_timeout() || e.is_request() => {
+				policy.backoff_for(retry)
+			}
+			_ => return Ok(result?),
+		};
+		tokio::time::sleep(delay).await;
+		retry += 1;
+	}
+}
+
+fn should_retry(status: StatusCode) -> bool {
+	status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+/// The delay requested by a `Retry-After` header given in seconds.
+fn retry_after(response: &Response) -> Option<Duration> {
+	let seconds = response.headers().get(RETRY_AFTER)?.to_str().ok()?;
+	seconds.trim().parse().ok().map(Duration::from_secs)
+}
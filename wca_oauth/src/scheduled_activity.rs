@@ -0,0 +1,83 @@
+use std::fmt::{Display, Formatter};
+
+use crate::wcif::show_local_time;
+use crate::wcif_container::ActivityIter;
+use crate::*;
+
+/// An activity together with the venue and room it takes place in, which is needed to know its
+/// local time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScheduledActivity<'a> {
+	pub venue: &'a Venue,
+	pub room: &'a Room,
+	pub activity: &'a Activity,
+}
+
+#[cfg(feature = "tz")]
+impl<'a> ScheduledActivity<'a> {
+	/// See `Activity::local_start`.
+	pub fn local_start(&self) -> Option<LocalDateTime> {
+		self.activity.local_start(self.venue)
+	}
+
+	pub fn local_end(&self) -> Option<LocalDateTime> {
+		self.activity.local_end(self.venue)
+	}
+}
+
+/// Displays the activity with its local times, e.g.
+/// `3x3x3 Cube, Round 1 in Main Room, 2024-05-04 09:30:00 CEST - 2024-05-04 10:30:00 CEST`.
+/// Times are shown in UTC if the time zone of the venue is unknown.
+impl<'a> Display for ScheduledActivity<'a> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		let timezone = &self.venue.timezone;
+		write!(
+			f,
+			"{} in {}, {} - {}",
+			self.activity.name,
+			self.room.name,
+			show_local_time(self.activity.start_time, timezone),
+			show_local_time(self.activity.end_time, timezone)
+		)
+	}
+}
+
+impl WcifContainer {
+	/// The top level activities of the schedule with their venue and room.
+	pub fn scheduled_activities(&self) -> impl Iterator<Item = ScheduledActivity<'_>> {
+		self.rooms().flat_map(|(venue, room)| {
+			room.activities
+				.iter()
+				.map(move |activity| ScheduledActivity {
+					venue,
+					room,
+					activity,
+				})
+		})
+	}
+
+	/// Like `scheduled_activities`, but also includes child activities, such as groups.
+	pub fn all_scheduled_activities(&self) -> impl Iterator<Item = ScheduledActivity<'_>> {
+		self.rooms().flat_map(|(venue, room)| {
+			ActivityIter::new(&room.activities).map(move |activity| ScheduledActivity {
+				venue,
+				room,
+				activity,
+			})
+		})
+	}
+
+	/// The activity with the given id.
+	pub fn scheduled_activity(&self, id: usize) -> Option<ScheduledActivity<'_>> {
+		self.all_scheduled_activities()
+			.find(|scheduled| scheduled.activity.id == id)
+	}
+
+	fn rooms(&self) -> impl Iterator<Item = (&Venue, &Room)> {
+		self.wcif
+			.schedule
+			.venues
+			.iter()
+			.flat_map(|venue| venue.rooms.iter().map(move |room| (venue, room)))
+	}
+}
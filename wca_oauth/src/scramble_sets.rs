@@ -0,0 +1,148 @@
+use std::fmt::{Display, Formatter};
+
+use crate::*;
+
+/// A round whose number of groups does not fit its scramble sets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScrambleSetMismatch {
+	pub round_id: String,
+	/// The number of groups across all rooms.
+	pub groups: usize,
+	/// `scramble_set_count` when checking counts, or the number of uploaded scramble sets when
+	/// linking.
+	pub scramble_sets: usize,
+}
+
+impl Display for ScrambleSetMismatch {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		write!(
+			f,
+			"{} has {} groups but {} scramble sets",
+			self.round_id, self.groups, self.scramble_sets
+		)
+	}
+}
+
+impl std::error::Error for ScrambleSetMismatch {}
+
+/// Why the scramble sets of a round could not be linked to its groups.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinkScrambleSetsError {
+	/// No round in the wcif has this id.
+	UnknownRound(String),
+	/// The round has fewer scramble sets than groups.
+	Mismatch(ScrambleSetMismatch),
+}
+
+impl Display for LinkScrambleSetsError {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		match self {
+			LinkScrambleSetsError::UnknownRound(round_id) => {
+				write!(f, "there is no round {round_id}")
+			}
+			LinkScrambleSetsError::Mismatch(mismatch) => write!(f, "{mismatch}"),
+		}
+	}
+}
+
+impl std::error::Error for LinkScrambleSetsError {}
+
+impl WcifContainer {
+	/// The groups of a round as pairs of room id and group number, in the order scramble sets are
+	/// linked to them: by room in schedule order, then by group number. Fewest moves and
+	/// multi-blind groups with an activity per attempt are only counted once.
+	pub fn round_groups(&self, round_id: &str) -> Vec<(usize, usize)> {
+		let mut groups = Vec::new();
+		for room in self
+			.wcif
+			.schedule
+			.venues
+			.iter()
+			.flat_map(|venue| &venue.rooms)
+		{
+			let mut numbers: Vec<_> = ActivityIter::new(&room.activities)
+				.filter(|activity| activity.activity_code.round_id().as_deref() == Some(round_id))
+				.filter_map(|activity| activity.activity_code.group_number())
+				.collect();
+			numbers.sort_unstable();
+			numbers.dedup();
+			groups.extend(numbers.into_iter().map(|number| (room.id, number)));
+		}
+		groups
+	}
+
+	/// Sets the `scramble_set_id` of every group activity of a round, using the scramble sets of
+	/// the round in the order of `round_groups`. Fails without changing anything if there are
+	/// fewer scramble sets than groups, or if the round does not exist.
+	pub fn link_scramble_sets(
+		&mut self,
+		round_id: &str,
+	) -> std::result::Result<(), LinkScrambleSetsError> {
+		let groups = self.round_groups(round_id);
+		let set_ids: Vec<_> = self
+			.round_iter()
+			.find(|round| round.id == round_id)
+			.map(|round| round.scramble_sets.iter().map(|set| set.id).collect())
+			.ok_or_else(|| LinkScrambleSetsError::UnknownRound(round_id.to_string()))?;
+		if set_ids.len() < groups.len() {
+			return Err(LinkScrambleSetsError::Mismatch(ScrambleSetMismatch {
+				round_id: round_id.to_string(),
+				groups: groups.len(),
+				scramble_sets: set_ids.len(),
+			}));
+		}
+		for room in self
+			.wcif
+			.schedule
+			.venues
+			.iter_mut()
+			.flat_map(|venue| &mut venue.rooms)
+		{
+			let room_id = room.id;
+			link(&mut room.activities, round_id, &|number| {
+				groups
+					.iter()
+					.position(|group| *group == (room_id, number))
+					.map(|index| set_ids[index])
+			});
+		}
+		Ok(())
+	}
+
+	/// The scramble set linked to a group activity.
+	pub fn scramble_set_of(&self, activity: &Activity) -> Option<&ScrambleSet> {
+		let round_id = activity.activity_code.round_id()?;
+		let set_id = activity.scramble_set_id?;
+		self.round_iter()
+			.find(|round| round.id == round_id)?
+			.scramble_sets
+			.iter()
+			.find(|set| set.id == set_id)
+	}
+
+	/// The rounds where `scramble_set_count` differs from the number of groups. Rounds without
+	/// groups are not checked.
+	pub fn check_scramble_set_counts(&self) -> Vec<ScrambleSetMismatch> {
+		self.round_iter()
+			.filter_map(|round| {
+				let groups = self.round_groups(&round.id).len();
+				(groups != 0 && groups != round.scramble_set_count).then(|| ScrambleSetMismatch {
+					round_id: round.id.clone(),
+					groups,
+					scramble_sets: round.scramble_set_count,
+				})
+			})
+			.collect()
+	}
+}
+
+fn link(activities: &mut [Activity], round_id: &str, set_id: &dyn Fn(usize) -> Option<u64>) {
+	for activity in activities {
+		if activity.activity_code.round_id().as_deref() == Some(round_id) {
+			if let Some(number) = activity.activity_code.group_number() {
+				activity.scramble_set_id = set_id(number);
+			}
+		}
+		link(&mut activity.child_activities, round_id, set_id);
+	}
+}
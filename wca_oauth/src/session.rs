@@ -0,0 +1,125 @@
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Write};
+use std::path::Path;
+use std::time::{Duration, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{ApiResult, OAuth, OAuthBuilder};
+
+/// The on disk format of a saved `OAuth` session.
+#[derive(Serialize, Deserialize)]
+struct Session {
+	client_id: String,
+	client_secret: String,
+	redirect_uri: String,
+	auth_url: String,
+	api_url: String,
+	access_token: String,
+	refresh_token: String,
+	/// Seconds since the unix epoch.
+	expires_at: Option<u64>,
+}
+
+impl OAuth {
+	/// Writes the session to `path`, so it can be restored with `OAuth::load`. The file contains
+	/// the tokens and the client secret, so on unix it is made readable by the owner only.
+	pub async fn save(&self, path: impl AsRef<Path>) -> ApiResult<()> {
+		let tokens = self.tokens.lock().await;
+		let session = Session {
+			client_id: self.client_id.clone(),
+			client_secret: self.client_secret.clone(),
+			redirect_uri: self.redirect_uri.clone(),
+			auth_url: self.auth_url.clone(),
+			api_url: self.api_url.clone(),
+			access_token: tokens.access_token.clone(),
+			refresh_token: tokens.refresh_token.clone(),
+			expires_at: tokens.expires_at.map(|expires_at| {
+				expires_at
+					.duration_since(UNIX_EPOCH)
+					.unwrap_or_default()
+					.as_secs()
+			}),
+		};
+		drop(tokens);
+
+		let json = serde_json::to_string_pretty(&session)?;
+		let mut options = OpenOptions::new();
+		options.write(true).create(true).truncate(true);
+		#[cfg(unix)]
+		std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+		let mut file = options.open(path)?;
+		#[cfg(unix)]
+		file.set_permissions(std::os::unix::fs::PermissionsExt::from_mode(0o600))?;
+		file.write_all(json.as_bytes())?;
+		Ok(())
+	}
+
+	/// Restores a session written by `save`. If the access token expired or is about to, it is
+	/// refreshed before returning. Uses the default http client and retry policy, see
+	/// `OAuthBuilder::load` to configure them.
+	pub async fn load(path: impl AsRef<Path>) -> ApiResult<OAuth> {
+		OAuthBuilder::new(String::new(), String::new())
+			.load(path)
+			.await
+	}
+
+	/// Same as `load`, but refuses to read a session file that other users can access. Only checks
+	/// permissions on unix.
+	pub async fn load_private(path: impl AsRef<Path>) -> ApiResult<OAuth> {
+		OAuthBuilder::new(String::new(), String::new())
+			.load_private(path)
+			.await
+	}
+}
+
+impl OAuthBuilder {
+	/// Restores a session written by `OAuth::save` with the http client, retry policy and snapshot
+	/// store of this builder. The client id, redirect uri and urls are those of the session.
+	pub async fn load(self, path: impl AsRef<Path>) -> ApiResult<OAuth> {
+		self.load_from(File::open(path)?).await
+	}
+
+	/// Same as `load`, but refuses to read a session file that other users can access. Only checks
+	/// permissions on unix.
+	pub async fn load_private(self, path: impl AsRef<Path>) -> ApiResult<OAuth> {
+		let file = File::open(path.as_ref())?;
+		#[cfg(unix)]
+		{
+			use std::os::unix::fs::PermissionsExt;
+			let mode = file.metadata()?.permissions().mode();
+			if mode & 0o077 != 0 {
+				return Err(std::io::Error::new(
+					std::io::ErrorKind::PermissionDenied,
+					format!(
+						"session file {} is accessible by other users (mode {:o})",
+						path.as_ref().display(),
+						mode & 0o777
+					),
+				)
+				.into());
+			}
+		}
+		self.load_from(file).await
+	}
+
+	async fn load_from(self, mut file: File) -> ApiResult<OAuth> {
+		let mut json = String::new();
+		file.read_to_string(&mut json)?;
+		let session: Session = serde_json::from_str(&json)?;
+
+		let mut oauth = self.build(session.access_token);
+		let tokens = oauth.tokens.get_mut();
+		tokens.refresh_token = session.refresh_token;
+		tokens.expires_at = session
+			.expires_at
+			.map(|secs| UNIX_EPOCH + Duration::from_secs(secs));
+		oauth.client_id = session.client_id;
+		oauth.client_secret = session.client_secret;
+		oauth.redirect_uri = session.redirect_uri;
+		oauth.auth_url = session.auth_url;
+		oauth.api_url = session.api_url;
+		oauth.access_token().await?;
+		Ok(oauth)
+	}
+}
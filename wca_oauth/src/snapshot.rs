@@ -0,0 +1,171 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufReader, BufWriter, ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+/// A directory of wcif snapshots. Every competition gets a subdirectory, with one file per
+/// snapshot named after the time it was taken, e.g. `snapshots/WC2023/1690000000000.json`.
+/// Snapshots taken in the same millisecond get a sequence number, e.g. `1690000000000-1.json`.
+///
+/// When set on an `OAuth` with `OAuthBuilder::snapshot_store`, the remote wcif is saved before
+/// every patch.
+#[derive(Debug, Clone)]
+pub struct SnapshotStore {
+	dir: PathBuf,
+}
+
+/// A saved wcif. The wcif itself is only read by `SnapshotStore::load`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snapshot {
+	pub competition_id: String,
+	pub taken_at: DateTime,
+	/// The operation that led to the snapshot, e.g. `patch`.
+	pub reason: String,
+	path: PathBuf,
+}
+
+impl Snapshot {
+	pub fn path(&self) -> &Path {
+		&self.path
+	}
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SnapshotFile<W> {
+	#[serde(
+		deserialize_with = "crate::de_date_time",
+		serialize_with = "crate::ser_date_time"
+	)]
+	taken_at: DateTime,
+	reason: String,
+	wcif: W,
+}
+
+impl SnapshotStore {
+	pub fn new(dir: impl Into<PathBuf>) -> SnapshotStore {
+		SnapshotStore { dir: dir.into() }
+	}
+
+	pub fn dir(&self) -> &Path {
+		&self.dir
+	}
+
+	/// Saves `wcif` as a snapshot of the competition with its id.
+	pub fn save(&self, wcif: &Wcif, reason: &str) -> ApiResult<Snapshot> {
+		let dir = self.dir.join(&wcif.id);
+		fs::create_dir_all(&dir)?;
+		let millis = SystemTime::now()
+			.duration_since(UNIX_EPOCH)
+			.unwrap_or_default()
+			.as_millis();
+		let (path, file) = create_new(&dir, millis)?;
+		let taken_at = chrono::DateTime::from_timestamp_millis(millis as i64)
+			.unwrap_or_default()
+			.naive_utc();
+		let contents = SnapshotFile {
+			taken_at,
+			reason: reason.to_string(),
+			wcif,
+		};
+		let mut writer = BufWriter::new(file);
+		serde_json::to_writer(&mut writer, &contents)?;
+		// Dropping the writer would silently lose a failed write, and the snapshot is only useful
+		// if it is still there after a crash.
+		writer.flush()?;
+		writer.get_ref().sync_all()?;
+		Ok(Snapshot {
+			competition_id: wcif.id.clone(),
+			taken_at,
+			reason: contents.reason,
+			path,
+		})
+	}
+
+	/// The snapshots of a competition, oldest first.
+	pub fn list(&self, competition_id: &str) -> ApiResult<Vec<Snapshot>> {
+		let dir = self.dir.join(competition_id);
+		if !dir.exists() {
+			return Ok(Vec::new());
+		}
+		let mut snapshots = Vec::new();
+		for entry in fs::read_dir(dir)? {
+			let path = entry?.path();
+			if path.extension() != Some("json".as_ref()) {
+				continue;
+			}
+			let file: SnapshotFile<serde::de::IgnoredAny> = read(&path)?;
+			snapshots.push(Snapshot {
+				competition_id: competition_id.to_string(),
+				taken_at: file.taken_at,
+				reason: file.reason,
+				path,
+			});
+		}
+		snapshots.sort_by_key(|snapshot| (snapshot.taken_at, sequence(&snapshot.path)));
+		Ok(snapshots)
+	}
+
+	/// The most recent snapshot of a competition.
+	pub fn latest(&self, competition_id: &str) -> ApiResult<Option<Snapshot>> {
+		Ok(self.list(competition_id)?.pop())
+	}
+
+	pub fn load(&self, snapshot: &Snapshot) -> ApiResult<Wcif> {
+		let mut file: SnapshotFile<Wcif> = read(&snapshot.path)?;
+		file.wcif.upgrade();
+		Ok(file.wcif)
+	}
+
+	/// What changed from snapshot `from` to snapshot `to`.
+	pub fn diff(&self, from: &Snapshot, to: &Snapshot) -> ApiResult<WcifDiff> {
+		Ok(diff(&self.load(from)?, &self.load(to)?))
+	}
+}
+
+/// Creates the file of a snapshot taken at `millis`, never overwriting an existing one.
+fn create_new(dir: &Path, millis: u128) -> ApiResult<(PathBuf, File)> {
+	let mut sequence = 0;
+	loop {
+		let path = match sequence {
+			0 => dir.join(format!("{millis}.json")),
+			_ => dir.join(format!("{millis}-{sequence}.json")),
+		};
+		match OpenOptions::new().write(true).create_new(true).open(&path) {
+			Ok(file) => return Ok((path, file)),
+			Err(e) if e.kind() == ErrorKind::AlreadyExists => sequence += 1,
+			Err(e) => return Err(e.into()),
+		}
+	}
+}
+
+/// The sequence number in the name of a snapshot file, 0 if it has none.
+fn sequence(path: &Path) -> usize {
+	path.file_stem()
+		.and_then(|stem| stem.to_str()?.split_once('-'))
+		.and_then(|(_, sequence)| sequence.parse().ok())
+		.unwrap_or(0)
+}
+
+fn read<T: for<'de> Deserialize<'de>>(path: &Path) -> ApiResult<T> {
+	Ok(serde_json::from_reader(BufReader::new(File::open(path)?))?)
+}
+
+impl WcifContainer {
+	/// Replaces the wcif with the snapshot and patches it. Like any patch, it is merged with
+	/// changes made remotely since the wcif was fetched.
+	pub async fn restore(
+		&mut self,
+		store: &SnapshotStore,
+		snapshot: &Snapshot,
+		oauth: &OAuth,
+	) -> ApiResult<String> {
+		self.wcif = store.load(snapshot)?;
+		let reason = format!("restore snapshot from {}", snapshot.taken_at);
+		self.patch_with_reason(oauth, &reason).await
+	}
+}
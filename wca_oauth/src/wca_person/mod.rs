@@ -0,0 +1,85 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Deserializer};
+
+use crate::{Avatar, EventId, OtherFields, PersonalBest, ResultValue, WcaId};
+
+/// A person as returned by the persons api.
+#[derive(Debug, PartialEq)]
+pub struct WcaPerson {
+	pub wca_id: WcaId,
+	pub name: String,
+	pub gender: String,
+	pub country_iso2: String,
+	pub avatar: Option<Avatar>,
+	pub competition_count: usize,
+	pub personal_bests: Vec<PersonalBest>,
+}
+
+#[derive(Deserialize)]
+struct PersonResponse {
+	person: PersonInfo,
+	competition_count: usize,
+	personal_records: HashMap<EventId, PersonalRecords>,
+}
+
+#[derive(Deserialize)]
+struct PersonInfo {
+	wca_id: WcaId,
+	name: String,
+	gender: String,
+	country_iso2: String,
+	avatar: Option<Avatar>,
+}
+
+#[derive(Deserialize)]
+struct PersonalRecords {
+	single: Option<PersonalRecord>,
+	average: Option<PersonalRecord>,
+}
+
+#[derive(Deserialize)]
+struct PersonalRecord {
+	best: ResultValue,
+	world_rank: usize,
+	continental_rank: usize,
+	national_rank: usize,
+}
+
+impl<'de> Deserialize<'de> for WcaPerson {
+	fn deserialize<D>(deserializer: D) -> Result<WcaPerson, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		let response = PersonResponse::deserialize(deserializer)?;
+		let mut personal_bests: Vec<_> = response
+			.personal_records
+			.into_iter()
+			.flat_map(|(event_id, records)| {
+				[("single", records.single), ("average", records.average)]
+					.into_iter()
+					.filter_map(move |(t, record)| {
+						record.map(|record| PersonalBest {
+							event_id: event_id.clone(),
+							value: record.best,
+							t: t.to_string(),
+							world_ranking: record.world_rank,
+							continental_ranking: record.continental_rank,
+							national_ranking: record.national_rank,
+							other: OtherFields::new(),
+						})
+					})
+			})
+			.collect();
+		personal_bests.sort_by(|a, b| a.event_id.cmp(&b.event_id).then(b.t.cmp(&a.t)));
+		Ok(WcaPerson {
+			wca_id: response.person.wca_id,
+			name: response.person.name,
+			gender: response.person.gender,
+			country_iso2: response.person.country_iso2,
+			avatar: response.person.avatar,
+			competition_count: response.competition_count,
+			personal_bests,
+		})
+	}
+}
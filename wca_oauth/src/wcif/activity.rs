@@ -1,4 +1,6 @@
-use super::DateTime;
+use super::{ActivityCode, DateTime, OtherFields};
+#[cfg(feature = "tz")]
+use super::{LocalDateTime, Venue};
 use serde::{Deserialize, Serialize};
 
 #[derive(PartialEq, Debug, Deserialize, Serialize, Eq)]
@@ -6,7 +8,7 @@ use serde::{Deserialize, Serialize};
 pub struct Activity {
 	pub id: usize,
 	pub name: String,
-	pub activity_code: String,
+	pub activity_code: ActivityCode,
 	#[serde(
 		deserialize_with = "crate::de_date_time",
 		serialize_with = "crate::ser_date_time"
@@ -18,8 +20,12 @@ pub struct Activity {
 	)]
 	pub end_time: DateTime,
 	pub child_activities: Vec<Activity>,
-	// pub scramble_set_id: Option<usize>, // Not implemented
+	/// The id of the `ScrambleSet` used by a group.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub scramble_set_id: Option<u64>,
 	pub extensions: Vec<serde_json::Value>,
+	#[serde(flatten)]
+	pub other: OtherFields,
 }
 
 impl Activity {
@@ -27,6 +33,18 @@ impl Activity {
 		self.start_time < other.end_time && other.start_time < self.end_time && self != other
 	}
 
+	/// The start time in the time zone of the venue the activity is in. `None` if the time zone
+	/// of the venue is unknown, in which case `start_time` is the only time there is, in UTC.
+	#[cfg(feature = "tz")]
+	pub fn local_start(&self, venue: &Venue) -> Option<LocalDateTime> {
+		venue.local_time(self.start_time)
+	}
+
+	#[cfg(feature = "tz")]
+	pub fn local_end(&self, venue: &Venue) -> Option<LocalDateTime> {
+		venue.local_time(self.end_time)
+	}
+
 	pub fn overlaps_any<'a>(&self, other: impl IntoIterator<Item = &'a Self> + 'a) -> bool {
 		other.into_iter().any(|other| self.overlaps(other))
 	}
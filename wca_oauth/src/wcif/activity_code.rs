@@ -0,0 +1,226 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::EventId;
+
+/// The code of an activity, e.g. `333-r2-g3-a1` or `other-lunch`. Parsing is strict, but when
+/// deserializing a code which is not in this format it is kept as `Unknown`, so wcifs written by
+/// other tools are patched back unchanged.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ActivityCode {
+	/// An activity of an event. The round, group and attempt are each optional, but appear in
+	/// that order.
+	Event {
+		event_id: EventId,
+		round: Option<usize>,
+		group: Option<usize>,
+		attempt: Option<usize>,
+	},
+	/// An activity which is not part of an event, e.g. `lunch` of `other-lunch`.
+	Other(String),
+	/// A code which is not valid, or not written the way the WCA writes it, e.g. `333-r01`.
+	Unknown(String),
+}
+
+impl ActivityCode {
+	pub fn event(event_id: EventId) -> ActivityCode {
+		ActivityCode::Event {
+			event_id,
+			round: None,
+			group: None,
+			attempt: None,
+		}
+	}
+
+	/// The code of a round, e.g. `333-r1`.
+	pub fn round(event_id: EventId, round: usize) -> ActivityCode {
+		ActivityCode::Event {
+			event_id,
+			round: Some(round),
+			group: None,
+			attempt: None,
+		}
+	}
+
+	/// Sets the group of the code. Does nothing for other activities.
+	pub fn with_group(mut self, group: usize) -> ActivityCode {
+		if let ActivityCode::Event { group: g, .. } = &mut self {
+			*g = Some(group);
+		}
+		self
+	}
+
+	/// Sets the attempt of the code. Does nothing for other activities.
+	pub fn with_attempt(mut self, attempt: usize) -> ActivityCode {
+		if let ActivityCode::Event { attempt: a, .. } = &mut self {
+			*a = Some(attempt);
+		}
+		self
+	}
+
+	pub fn event_id(&self) -> Option<&EventId> {
+		match self {
+			ActivityCode::Event { event_id, .. } => Some(event_id),
+			ActivityCode::Other(_) | ActivityCode::Unknown(_) => None,
+		}
+	}
+
+	pub fn round_number(&self) -> Option<usize> {
+		match self {
+			ActivityCode::Event { round, .. } => *round,
+			ActivityCode::Other(_) | ActivityCode::Unknown(_) => None,
+		}
+	}
+
+	pub fn group_number(&self) -> Option<usize> {
+		match self {
+			ActivityCode::Event { group, .. } => *group,
+			ActivityCode::Other(_) | ActivityCode::Unknown(_) => None,
+		}
+	}
+
+	pub fn attempt_number(&self) -> Option<usize> {
+		match self {
+			ActivityCode::Event { attempt, .. } => *attempt,
+			ActivityCode::Other(_) | ActivityCode::Unknown(_) => None,
+		}
+	}
+
+	/// The code of the round of the activity, without group and attempt.
+	pub fn round_code(&self) -> Option<ActivityCode> {
+		let event_id = self.event_id()?.clone();
+		Some(ActivityCode::round(event_id, self.round_number()?))
+	}
+
+	/// The id of the round of the activity, as used by `Round::id`.
+	pub fn round_id(&self) -> Option<String> {
+		self.round_code().map(|code| code.to_string())
+	}
+
+	/// Whether `other` is this activity or a part of it, e.g. `333-r1` contains `333-r1-g2`
+	/// but neither `333-r10` nor `333oh-r1`.
+	pub fn contains(&self, other: &ActivityCode) -> bool {
+		fn part_contains(part: Option<usize>, other: Option<usize>) -> bool {
+			part.is_none() || part == other
+		}
+		match (self, other) {
+			(
+				ActivityCode::Event {
+					event_id,
+					round,
+					group,
+					attempt,
+				},
+				ActivityCode::Event {
+					event_id: other_event_id,
+					round: other_round,
+					group: other_group,
+					attempt: other_attempt,
+				},
+			) => {
+				event_id == other_event_id
+					&& part_contains(*round, *other_round)
+					&& part_contains(*group, *other_group)
+					&& part_contains(*attempt, *other_attempt)
+			}
+			(ActivityCode::Other(name), ActivityCode::Other(other_name))
+			| (ActivityCode::Unknown(name), ActivityCode::Unknown(other_name)) => name == other_name,
+			_ => false,
+		}
+	}
+}
+
+/// The error of parsing an invalid activity code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseActivityCodeError {
+	pub code: String,
+}
+
+impl Display for ParseActivityCodeError {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		write!(f, "invalid activity code: {}", self.code)
+	}
+}
+
+impl std::error::Error for ParseActivityCodeError {}
+
+impl FromStr for ActivityCode {
+	type Err = ParseActivityCodeError;
+
+	fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+		let error = || ParseActivityCodeError {
+			code: s.to_string(),
+		};
+		if let Some(name) = s.strip_prefix("other-") {
+			return Ok(ActivityCode::Other(name.to_string()));
+		}
+		let mut parts = s.split('-');
+		let event_id = parts.next().filter(|id| !id.is_empty()).ok_or_else(error)?;
+		let mut numbers = [None; 3];
+		// The index of the next part which may appear, to keep them in order.
+		let mut next = 0;
+		for part in parts {
+			let index = ['r', 'g', 'a']
+				.iter()
+				.position(|prefix| part.starts_with(*prefix))
+				.filter(|index| *index >= next)
+				.ok_or_else(error)?;
+			numbers[index] = Some(part[1..].parse().map_err(|_| error())?);
+			next = index + 1;
+		}
+		let [round, group, attempt] = numbers;
+		Ok(ActivityCode::Event {
+			event_id: event_id.into(),
+			round,
+			group,
+			attempt,
+		})
+	}
+}
+
+impl Display for ActivityCode {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		match self {
+			ActivityCode::Event {
+				event_id,
+				round,
+				group,
+				attempt,
+			} => {
+				write!(f, "{event_id}")?;
+				for (prefix, number) in [('r', round), ('g', group), ('a', attempt)] {
+					if let Some(number) = number {
+						write!(f, "-{prefix}{number}")?;
+					}
+				}
+				Ok(())
+			}
+			ActivityCode::Other(name) => write!(f, "other-{name}"),
+			ActivityCode::Unknown(code) => f.write_str(code),
+		}
+	}
+}
+
+impl<'de> Deserialize<'de> for ActivityCode {
+	fn deserialize<D>(deserializer: D) -> std::result::Result<ActivityCode, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		let s: String = Deserialize::deserialize(deserializer)?;
+		Ok(match s.parse::<ActivityCode>() {
+			Ok(code) if code.to_string() == s => code,
+			_ => ActivityCode::Unknown(s),
+		})
+	}
+}
+
+impl Serialize for ActivityCode {
+	fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		serializer.collect_str(self)
+	}
+}
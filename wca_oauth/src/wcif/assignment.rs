@@ -1,17 +1,21 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize, Serialize, Debug, PartialEq)]
+use crate::OtherFields;
+
+#[derive(Deserialize, Serialize, Debug, PartialEq, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Assignment {
 	pub activity_id: usize,
 	pub assignment_code: AssignmentCode,
 	pub station_number: Option<usize>,
+	#[serde(flatten)]
+	pub other: OtherFields,
 }
 
 use serde::de::Visitor;
 use serde::Deserializer;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub enum AssignmentCode {
 	Competitor,
 	Judge,
@@ -61,14 +65,27 @@ impl Serialize for AssignmentCode {
 	where
 		S: serde::Serializer,
 	{
-		serializer.serialize_str(match self {
+		serializer.serialize_str(self.as_str())
+	}
+}
+
+impl AssignmentCode {
+	/// The assignment code as it is written in the wcif, e.g. `staff-judge`.
+	pub fn as_str(&self) -> &str {
+		match self {
 			AssignmentCode::Competitor => "competitor",
 			AssignmentCode::Judge => "staff-judge",
 			AssignmentCode::Scrambler => "staff-scrambler",
 			AssignmentCode::Runner => "staff-runner",
 			AssignmentCode::DataEntry => "staff-dataentry",
 			AssignmentCode::Announcer => "staff-announcer",
-			AssignmentCode::Other(v) => &v,
-		})
+			AssignmentCode::Other(v) => v,
+		}
+	}
+}
+
+impl std::fmt::Display for AssignmentCode {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str(self.as_str())
 	}
 }
@@ -1,8 +1,14 @@
 use serde::{Deserialize, Serialize};
 
+use crate::OtherFields;
+
 #[derive(Deserialize, Serialize, Debug, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct Avatar {
 	pub url: String,
+	// The competitions api uses snake case.
+	#[serde(alias = "thumb_url")]
 	pub thumb_url: String,
+	#[serde(flatten)]
+	pub other: OtherFields,
 }
@@ -1,10 +1,13 @@
 use serde::{Deserialize, Serialize};
 
-use crate::ResultValue;
+use crate::{OtherFields, ResultValue};
 
 #[derive(Clone, Deserialize, Serialize, Debug, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct Cutoff {
 	pub number_of_attempts: usize,
+	#[serde(rename = "attemptResult", alias = "resultValue")]
 	pub result_value: ResultValue,
+	#[serde(flatten)]
+	pub other: OtherFields,
 }
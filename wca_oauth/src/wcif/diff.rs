@@ -0,0 +1,386 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+use super::*;
+use crate::change_summary::assignment_changes;
+use crate::wcif_container::ActivityIter;
+use crate::AssignmentChanges;
+
+/// The meaningful changes between two versions of a wcif, see `diff`. Printing it gives a text
+/// report.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WcifDiff {
+	pub persons_added: Vec<PersonSummary>,
+	pub persons_removed: Vec<PersonSummary>,
+	pub registration_status: Vec<RegistrationStatusChange>,
+	pub activities_added: Vec<ActivityDiff>,
+	pub activities_removed: Vec<ActivityDiff>,
+	/// Activities which exist in both versions but start or end at a different time.
+	pub time_shifts: Vec<TimeShift>,
+	pub assignments: Vec<AssignmentChanges>,
+	pub results: Vec<ResultChange>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PersonSummary {
+	pub wca_user_id: usize,
+	pub registrant_id: Option<usize>,
+	pub name: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RegistrationStatusChange {
+	pub person: PersonSummary,
+	/// `None` if the person was not registered.
+	pub before: Option<String>,
+	pub after: Option<String>,
+}
+
+/// An activity and the room it is in. Times are in UTC like in the wcif, and shown in the time
+/// zone of the venue.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActivityDiff {
+	pub id: usize,
+	pub activity_code: ActivityCode,
+	pub room: String,
+	pub name: String,
+	pub start_time: DateTime,
+	pub end_time: DateTime,
+	/// The time zone of the venue, see `Venue::timezone`.
+	pub timezone: String,
+}
+
+/// Start and end time of an activity before and after the change, in UTC.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeShift {
+	pub id: usize,
+	pub activity_code: ActivityCode,
+	pub room: String,
+	pub timezone: String,
+	pub before: (DateTime, DateTime),
+	pub after: (DateTime, DateTime),
+}
+
+/// A result of a person in a round which was entered, removed or changed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResultChange {
+	pub round_id: String,
+	pub person_id: usize,
+	pub before: Option<ResultSummary>,
+	pub after: Option<ResultSummary>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResultSummary {
+	pub ranking: Option<usize>,
+	pub best: ResultValue,
+	pub average: ResultValue,
+}
+
+/// Compares two versions of a wcif, e.g. two fetches of the same competition. Persons are matched
+/// by wca user id, activities by id and results by round and person.
+pub fn diff(before: &Wcif, after: &Wcif) -> WcifDiff {
+	let mut diff = WcifDiff::default();
+
+	for person in &after.persons {
+		match before
+			.persons
+			.iter()
+			.find(|p| p.wca_user_id == person.wca_user_id)
+		{
+			None => diff.persons_added.push(PersonSummary::of(person)),
+			Some(previous) => {
+				let status = |p: &Person| p.registration.as_ref().map(|r| r.status.clone());
+				if status(previous) != status(person) {
+					diff.registration_status.push(RegistrationStatusChange {
+						person: PersonSummary::of(person),
+						before: status(previous),
+						after: status(person),
+					});
+				}
+			}
+		}
+	}
+	diff.persons_removed = before
+		.persons
+		.iter()
+		.filter(|p| !after.persons.iter().any(|a| a.wca_user_id == p.wca_user_id))
+		.map(PersonSummary::of)
+		.collect();
+
+	let before_activities = activities(before);
+	let after_activities = activities(after);
+	for (id, activity) in &after_activities {
+		match before_activities.get(id) {
+			None => diff.activities_added.push(activity.clone()),
+			Some(previous)
+				if (previous.start_time, previous.end_time)
+					!= (activity.start_time, activity.end_time) =>
+			{
+				diff.time_shifts.push(TimeShift {
+					id: activity.id,
+					activity_code: activity.activity_code.clone(),
+					room: activity.room.clone(),
+					timezone: activity.timezone.clone(),
+					before: (previous.start_time, previous.end_time),
+					after: (activity.start_time, activity.end_time),
+				})
+			}
+			Some(_) => (),
+		}
+	}
+	diff.activities_removed = before_activities
+		.iter()
+		.filter(|(id, _)| !after_activities.contains_key(*id))
+		.map(|(_, activity)| activity.clone())
+		.collect();
+	for activities in [&mut diff.activities_added, &mut diff.activities_removed] {
+		activities.sort_by(|a, b| {
+			(a.start_time, &a.activity_code, a.id).cmp(&(b.start_time, &b.activity_code, b.id))
+		});
+	}
+	diff.time_shifts
+		.sort_by_key(|shift| (shift.after.0, shift.id));
+
+	diff.assignments = assignment_changes(before, after);
+
+	let before_rounds = rounds(before);
+	for round in after.events.iter().flat_map(|event| &event.rounds) {
+		let previous = before_rounds
+			.get(round.id.as_str())
+			.map(|r| r.results.as_slice())
+			.unwrap_or_default();
+		diff.results
+			.extend(result_changes(&round.id, previous, &round.results));
+	}
+	let after_rounds = rounds(after);
+	for round in before.events.iter().flat_map(|event| &event.rounds) {
+		if !after_rounds.contains_key(round.id.as_str()) {
+			diff.results
+				.extend(result_changes(&round.id, &round.results, &[]));
+		}
+	}
+
+	diff
+}
+
+impl WcifDiff {
+	pub fn is_empty(&self) -> bool {
+		self == &WcifDiff::default()
+	}
+}
+
+impl PersonSummary {
+	fn of(person: &Person) -> PersonSummary {
+		PersonSummary {
+			wca_user_id: person.wca_user_id,
+			registrant_id: person.registrant_id,
+			name: person.name.clone(),
+		}
+	}
+}
+
+impl ResultSummary {
+	fn of(result: &Result) -> ResultSummary {
+		ResultSummary {
+			ranking: result.ranking,
+			best: result.best,
+			average: result.average,
+		}
+	}
+}
+
+fn activities(wcif: &Wcif) -> HashMap<usize, ActivityDiff> {
+	wcif.schedule
+		.venues
+		.iter()
+		.flat_map(|venue| venue.rooms.iter().map(move |room| (venue, room)))
+		.flat_map(|(venue, room)| {
+			ActivityIter::new(&room.activities).map(move |activity| {
+				(
+					activity.id,
+					ActivityDiff {
+						id: activity.id,
+						activity_code: activity.activity_code.clone(),
+						room: room.name.clone(),
+						name: activity.name.clone(),
+						start_time: activity.start_time,
+						end_time: activity.end_time,
+						timezone: venue.timezone.clone(),
+					},
+				)
+			})
+		})
+		.collect()
+}
+
+fn rounds(wcif: &Wcif) -> HashMap<&str, &Round> {
+	wcif.events
+		.iter()
+		.flat_map(|event| &event.rounds)
+		.map(|round| (round.id.as_str(), round))
+		.collect()
+}
+
+fn result_changes(round_id: &str, before: &[Result], after: &[Result]) -> Vec<ResultChange> {
+	let mut person_ids: Vec<usize> = after.iter().map(|r| r.person_id).collect();
+	person_ids.extend(
+		before
+			.iter()
+			.map(|r| r.person_id)
+			.filter(|id| !after.iter().any(|r| r.person_id == *id)),
+	);
+	person_ids
+		.into_iter()
+		.filter_map(|person_id| {
+			let previous = before.iter().find(|r| r.person_id == person_id);
+			let current = after.iter().find(|r| r.person_id == person_id);
+			(previous != current).then(|| ResultChange {
+				round_id: round_id.to_string(),
+				person_id,
+				before: previous.map(ResultSummary::of),
+				after: current.map(ResultSummary::of),
+			})
+		})
+		.collect()
+}
+
+impl Display for WcifDiff {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		if self.is_empty() {
+			return writeln!(f, "No changes.");
+		}
+		if !self.persons_added.is_empty() || !self.persons_removed.is_empty() {
+			writeln!(f, "Persons:")?;
+			for person in &self.persons_added {
+				writeln!(f, "  + {person}")?;
+			}
+			for person in &self.persons_removed {
+				writeln!(f, "  - {person}")?;
+			}
+		}
+		if !self.registration_status.is_empty() {
+			writeln!(f, "Registration status:")?;
+			for change in &self.registration_status {
+				let show = |status: &Option<String>| {
+					status
+						.clone()
+						.unwrap_or_else(|| "not registered".to_string())
+				};
+				writeln!(
+					f,
+					"  {}: {} -> {}",
+					change.person,
+					show(&change.before),
+					show(&change.after)
+				)?;
+			}
+		}
+		if !self.activities_added.is_empty() || !self.activities_removed.is_empty() {
+			writeln!(f, "Activities:")?;
+			for activity in &self.activities_added {
+				writeln!(f, "  + {activity}")?;
+			}
+			for activity in &self.activities_removed {
+				writeln!(f, "  - {activity}")?;
+			}
+		}
+		if !self.time_shifts.is_empty() {
+			writeln!(f, "Schedule:")?;
+			for shift in &self.time_shifts {
+				let show = |time| show_local_time(time, &shift.timezone);
+				writeln!(
+					f,
+					"  {} in {}: {} - {} -> {} - {}",
+					shift.activity_code,
+					shift.room,
+					show(shift.before.0),
+					show(shift.before.1),
+					show(shift.after.0),
+					show(shift.after.1)
+				)?;
+			}
+		}
+		if !self.assignments.is_empty() {
+			writeln!(f, "Assignments:")?;
+			for person in &self.assignments {
+				writeln!(f, "  {}:", person.name)?;
+				for assignment in &person.added {
+					writeln!(f, "    + {assignment}")?;
+				}
+				for assignment in &person.removed {
+					writeln!(f, "    - {assignment}")?;
+				}
+			}
+		}
+		if !self.results.is_empty() {
+			writeln!(f, "Results:")?;
+			let mut round_id = None;
+			for change in &self.results {
+				if round_id != Some(&change.round_id) {
+					writeln!(f, "  {}:", change.round_id)?;
+					round_id = Some(&change.round_id);
+				}
+				match (&change.before, &change.after) {
+					(None, Some(after)) => {
+						writeln!(f, "    + registrant {}: {after}", change.person_id)?
+					}
+					(Some(before), None) => {
+						writeln!(f, "    - registrant {}: {before}", change.person_id)?
+					}
+					(Some(before), Some(after)) => writeln!(
+						f,
+						"    ~ registrant {}: {before} -> {after}",
+						change.person_id
+					)?,
+					(None, None) => (),
+				}
+			}
+		}
+		Ok(())
+	}
+}
+
+impl Display for PersonSummary {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		match self.registrant_id {
+			Some(id) => write!(f, "{} (registrant {id})", self.name),
+			None => write!(f, "{}", self.name),
+		}
+	}
+}
+
+impl Display for ActivityDiff {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		write!(
+			f,
+			"{} ({}) in {}, {} - {}",
+			self.activity_code,
+			self.name,
+			self.room,
+			show_local_time(self.start_time, &self.timezone),
+			show_local_time(self.end_time, &self.timezone)
+		)
+	}
+}
+
+impl Display for ResultSummary {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		let show = |value: ResultValue| match value {
+			ResultValue::Ok(value) => value.to_string(),
+			ResultValue::DNF => "DNF".to_string(),
+			ResultValue::DNS => "DNS".to_string(),
+			ResultValue::Skip => "-".to_string(),
+		};
+		write!(
+			f,
+			"best {}, average {}",
+			show(self.best),
+			show(self.average)
+		)?;
+		if let Some(ranking) = self.ranking {
+			write!(f, ", ranked {ranking}")?;
+		}
+		Ok(())
+	}
+}
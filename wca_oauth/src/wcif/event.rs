@@ -4,9 +4,17 @@ use super::*;
 
 #[derive(Deserialize, Serialize, Debug, PartialEq)]
 pub struct Event {
-	pub id: String,
+	pub id: EventId,
 	pub rounds: Vec<Round>,
-	// pub competitor_limit: Option<usize>, // Not implemented by WCA
+	/// Not sent by the WCA, but by some other tools.
+	#[serde(
+		rename = "competitorLimit",
+		default,
+		skip_serializing_if = "Option::is_none"
+	)]
+	pub competitor_limit: Option<usize>,
 	pub qualification: Option<Qualification>,
 	pub extensions: Vec<serde_json::Value>,
+	#[serde(flatten)]
+	pub other: OtherFields,
 }
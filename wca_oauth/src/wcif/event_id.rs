@@ -0,0 +1,214 @@
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::RoundFormat;
+
+/// The id of an event. Official events have their own variant, any other event is kept as
+/// `Other`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum EventId {
+	E333,
+	E222,
+	E444,
+	E555,
+	E666,
+	E777,
+	E333bf,
+	E333fm,
+	E333oh,
+	Clock,
+	Minx,
+	Pyram,
+	Skewb,
+	Sq1,
+	E444bf,
+	E555bf,
+	E333mbf,
+	Other(String),
+}
+
+impl EventId {
+	/// The official events in the order used by the WCA.
+	pub const OFFICIAL: [EventId; 17] = [
+		EventId::E333,
+		EventId::E222,
+		EventId::E444,
+		EventId::E555,
+		EventId::E666,
+		EventId::E777,
+		EventId::E333bf,
+		EventId::E333fm,
+		EventId::E333oh,
+		EventId::Clock,
+		EventId::Minx,
+		EventId::Pyram,
+		EventId::Skewb,
+		EventId::Sq1,
+		EventId::E444bf,
+		EventId::E555bf,
+		EventId::E333mbf,
+	];
+
+	pub fn as_str(&self) -> &str {
+		match self {
+			EventId::E333 => "333",
+			EventId::E222 => "222",
+			EventId::E444 => "444",
+			EventId::E555 => "555",
+			EventId::E666 => "666",
+			EventId::E777 => "777",
+			EventId::E333bf => "333bf",
+			EventId::E333fm => "333fm",
+			EventId::E333oh => "333oh",
+			EventId::Clock => "clock",
+			EventId::Minx => "minx",
+			EventId::Pyram => "pyram",
+			EventId::Skewb => "skewb",
+			EventId::Sq1 => "sq1",
+			EventId::E444bf => "444bf",
+			EventId::E555bf => "555bf",
+			EventId::E333mbf => "333mbf",
+			EventId::Other(id) => id,
+		}
+	}
+
+	/// The english name of the event. Unofficial events are named by their id.
+	pub fn name(&self) -> &str {
+		match self {
+			EventId::E333 => "3x3x3 Cube",
+			EventId::E222 => "2x2x2 Cube",
+			EventId::E444 => "4x4x4 Cube",
+			EventId::E555 => "5x5x5 Cube",
+			EventId::E666 => "6x6x6 Cube",
+			EventId::E777 => "7x7x7 Cube",
+			EventId::E333bf => "3x3x3 Blindfolded",
+			EventId::E333fm => "3x3x3 Fewest Moves",
+			EventId::E333oh => "3x3x3 One-Handed",
+			EventId::Clock => "Clock",
+			EventId::Minx => "Megaminx",
+			EventId::Pyram => "Pyraminx",
+			EventId::Skewb => "Skewb",
+			EventId::Sq1 => "Square-1",
+			EventId::E444bf => "4x4x4 Blindfolded",
+			EventId::E555bf => "5x5x5 Blindfolded",
+			EventId::E333mbf => "3x3x3 Multi-Blind",
+			EventId::Other(id) => id,
+		}
+	}
+
+	pub fn is_official(&self) -> bool {
+		!matches!(self, EventId::Other(_))
+	}
+
+	/// The round formats the event may be held in, in the order of preference of the WCA. Any
+	/// format is allowed for unofficial events.
+	pub fn allowed_formats(&self) -> &'static [RoundFormat] {
+		use RoundFormat::*;
+		match self {
+			EventId::E666 | EventId::E777 => &[Mean, BestOf3, BestOf2, BestOf1],
+			EventId::E333fm => &[Mean, BestOf2, BestOf1],
+			EventId::E333bf | EventId::E444bf | EventId::E555bf | EventId::E333mbf => {
+				&[BestOf3, BestOf2, BestOf1]
+			}
+			EventId::Other(_) => &[Average, Mean, BestOf3, BestOf2, BestOf1],
+			_ => &[Average, BestOf3, BestOf2, BestOf1],
+		}
+	}
+
+	/// Whether results are times. Fewest moves results are moves and multi-blind results are
+	/// points.
+	pub fn is_timed(&self) -> bool {
+		!self.is_counted_in_moves() && !self.is_multi_blind()
+	}
+
+	pub fn is_counted_in_moves(&self) -> bool {
+		*self == EventId::E333fm
+	}
+
+	pub fn is_multi_blind(&self) -> bool {
+		*self == EventId::E333mbf
+	}
+
+	/// The number of attempts in a round of the preferred format of the event.
+	pub fn default_attempts(&self) -> usize {
+		self.allowed_formats()[0]
+			.attempts()
+			.expect("the preferred formats have a fixed number of attempts")
+	}
+}
+
+impl From<&str> for EventId {
+	fn from(s: &str) -> Self {
+		EventId::OFFICIAL
+			.into_iter()
+			.find(|event| event.as_str() == s)
+			.unwrap_or_else(|| EventId::Other(s.to_string()))
+	}
+}
+
+impl From<String> for EventId {
+	fn from(s: String) -> Self {
+		s.as_str().into()
+	}
+}
+
+impl FromStr for EventId {
+	type Err = std::convert::Infallible;
+
+	fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+		Ok(s.into())
+	}
+}
+
+impl Display for EventId {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		f.write_str(self.as_str())
+	}
+}
+
+impl PartialEq<str> for EventId {
+	fn eq(&self, other: &str) -> bool {
+		self.as_str() == other
+	}
+}
+
+impl PartialEq<&str> for EventId {
+	fn eq(&self, other: &&str) -> bool {
+		self.as_str() == *other
+	}
+}
+
+/// Ordered by id, like the strings event ids used to be.
+impl PartialOrd for EventId {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl Ord for EventId {
+	fn cmp(&self, other: &Self) -> Ordering {
+		self.as_str().cmp(other.as_str())
+	}
+}
+
+impl<'de> Deserialize<'de> for EventId {
+	fn deserialize<D>(deserializer: D) -> std::result::Result<EventId, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		let s: String = Deserialize::deserialize(deserializer)?;
+		Ok(s.into())
+	}
+}
+
+impl Serialize for EventId {
+	fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		serializer.serialize_str(self.as_str())
+	}
+}
@@ -0,0 +1,95 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::*;
+
+/// The envelope of a wcif extension. The id is namespaced by the tool owning it, e.g.
+/// `groupifier.ActivityConfig`, and the spec url points to a json schema of the data.
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Extension {
+	pub id: String,
+	pub spec_url: String,
+	pub data: Value,
+	#[serde(flatten)]
+	pub other: OtherFields,
+}
+
+/// Access to the extensions of the wcif objects which have them. Extensions which are not valid
+/// envelopes are ignored when reading and kept when writing.
+pub trait Extensible {
+	fn extensions(&self) -> &[Value];
+
+	fn extensions_mut(&mut self) -> &mut Vec<Value>;
+
+	/// The envelope of the extension with the given id.
+	fn extension(&self, id: &str) -> Option<Extension> {
+		self.extensions()
+			.iter()
+			.filter(|extension| extension["id"] == id)
+			.find_map(|extension| Extension::deserialize(extension).ok())
+	}
+
+	/// The data of the extension with the given id, or `None` if there is no such extension.
+	/// Fails if the data does not fit `T`.
+	fn get_extension<T: DeserializeOwned>(&self, id: &str) -> serde_json::Result<Option<T>> {
+		self.extension(id)
+			.map(|extension| serde_json::from_value(extension.data))
+			.transpose()
+	}
+
+	/// Sets the data of the extension with the given id, adding the extension if it does not
+	/// exist.
+	fn set_extension<T: Serialize>(
+		&mut self,
+		id: &str,
+		spec_url: &str,
+		data: T,
+	) -> serde_json::Result<()> {
+		let data = serde_json::to_value(data)?;
+		let extensions = self.extensions_mut();
+		match extensions
+			.iter_mut()
+			.find_map(|extension| extension.as_object_mut().filter(|e| e["id"] == id))
+		{
+			Some(extension) => {
+				extension.insert("specUrl".to_string(), spec_url.into());
+				extension.insert("data".to_string(), data);
+			}
+			None => extensions.push(serde_json::to_value(Extension {
+				id: id.to_string(),
+				spec_url: spec_url.to_string(),
+				data,
+				other: OtherFields::new(),
+			})?),
+		}
+		Ok(())
+	}
+
+	/// Removes the extension with the given id. Returns whether it existed.
+	fn remove_extension(&mut self, id: &str) -> bool {
+		let extensions = self.extensions_mut();
+		let len = extensions.len();
+		extensions.retain(|extension| extension["id"] != id);
+		extensions.len() != len
+	}
+}
+
+macro_rules! impl_extensible {
+	($($t:ty),*) => {
+		$(
+			impl Extensible for $t {
+				fn extensions(&self) -> &[Value] {
+					&self.extensions
+				}
+
+				fn extensions_mut(&mut self) -> &mut Vec<Value> {
+					&mut self.extensions
+				}
+			}
+		)*
+	};
+}
+
+impl_extensible!(Wcif, Event, Round, Activity, Room, Venue, Person);
@@ -0,0 +1,209 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+
+use super::*;
+
+/// The version of the wcif specification a document follows. Versions before 2.1 describe how
+/// competitors advance with an `advancementCondition` on the previous round instead of a
+/// `participationRuleset` on the round itself.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum FormatVersion {
+	V1_0,
+	V2_0,
+	V2_1_1,
+	Other(String),
+}
+
+impl FormatVersion {
+	/// The version the model follows, and which is requested from the WCA.
+	pub const LATEST: FormatVersion = FormatVersion::V2_1_1;
+
+	pub fn as_str(&self) -> &str {
+		match self {
+			FormatVersion::V1_0 => "1.0",
+			FormatVersion::V2_0 => "2.0",
+			FormatVersion::V2_1_1 => "2.1.1",
+			FormatVersion::Other(version) => version,
+		}
+	}
+
+	/// Whether advancement is described by `advancementCondition`.
+	pub fn uses_advancement_conditions(&self) -> bool {
+		match self {
+			FormatVersion::V1_0 | FormatVersion::V2_0 => true,
+			FormatVersion::V2_1_1 => false,
+			FormatVersion::Other(version) => {
+				version.starts_with("1.") || version.starts_with("2.0")
+			}
+		}
+	}
+}
+
+impl Default for FormatVersion {
+	fn default() -> Self {
+		FormatVersion::LATEST
+	}
+}
+
+impl From<&str> for FormatVersion {
+	fn from(s: &str) -> Self {
+		match s {
+			"1.0" => FormatVersion::V1_0,
+			"2.0" => FormatVersion::V2_0,
+			"2.1.1" => FormatVersion::V2_1_1,
+			s => FormatVersion::Other(s.to_string()),
+		}
+	}
+}
+
+impl FromStr for FormatVersion {
+	type Err = std::convert::Infallible;
+
+	fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+		Ok(s.into())
+	}
+}
+
+impl Display for FormatVersion {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		f.write_str(self.as_str())
+	}
+}
+
+impl<'de> Deserialize<'de> for FormatVersion {
+	fn deserialize<D>(deserializer: D) -> std::result::Result<FormatVersion, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		let s: String = Deserialize::deserialize(deserializer)?;
+		Ok(s.as_str().into())
+	}
+}
+
+impl Serialize for FormatVersion {
+	fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		serializer.serialize_str(self.as_str())
+	}
+}
+
+impl Wcif {
+	/// Brings a wcif of an older version up to `FormatVersion::LATEST`. The `advancementCondition`
+	/// of each round becomes the `participationRuleset` of the next. Done by `parse` and when
+	/// fetching, so it is only needed for wcifs deserialized by other means.
+	pub fn upgrade(&mut self) {
+		if !self.format_version.uses_advancement_conditions() {
+			return;
+		}
+		for event in &mut self.events {
+			// The id, format and advancement condition of the previous round.
+			let mut previous: Option<(String, RoundFormat, Option<AdvancementCondition>)> = None;
+			for round in &mut event.rounds {
+				let condition = round
+					.other
+					.remove("advancementCondition")
+					.and_then(|condition| serde_json::from_value(condition).ok());
+				if round.participation_ruleset.is_none() {
+					let participation_source = match previous.take() {
+						None => Some(ParticipationSource::Registrations),
+						Some((round_id, format, Some(condition))) => {
+							Some(ParticipationSource::Round {
+								round_id,
+								result_condition: condition
+									.to_result_condition(format.ranking().scope()),
+							})
+						}
+						Some((_, _, None)) => None,
+					};
+					round.participation_ruleset = Some(ParticipationRuleset {
+						participation_source,
+						reserved_places: None,
+						other: OtherFields::new(),
+					});
+				}
+				previous = Some((round.id.clone(), round.format, condition));
+			}
+		}
+		self.format_version = FormatVersion::LATEST;
+	}
+
+	/// Serializes the wcif as a document of `version`. For versions using advancement conditions,
+	/// the participation rulesets are turned back into the `advancementCondition` of the previous
+	/// round.
+	pub fn to_json_version(&self, version: &FormatVersion) -> serde_json::Result<Value> {
+		let mut json = serde_json::to_value(self)?;
+		json["formatVersion"] = Value::String(version.as_str().to_string());
+		if !version.uses_advancement_conditions() {
+			return Ok(json);
+		}
+		for (event, event_json) in self
+			.events
+			.iter()
+			.zip(json["events"].as_array_mut().into_iter().flatten())
+		{
+			let rounds_json = event_json["rounds"].as_array_mut().into_iter().flatten();
+			for (index, round_json) in rounds_json.enumerate() {
+				let condition = event
+					.rounds
+					.get(index + 1)
+					.and_then(|next| next.participation_ruleset.as_ref())
+					.and_then(|ruleset| ruleset.participation_source.as_ref())
+					.and_then(|source| match source {
+						ParticipationSource::Round {
+							result_condition, ..
+						} => AdvancementCondition::from_result_condition(result_condition),
+						_ => None,
+					});
+				if let Some(round_json) = round_json.as_object_mut() {
+					round_json.remove("participationRuleset");
+					round_json.insert(
+						"advancementCondition".to_string(),
+						serde_json::to_value(condition)?,
+					);
+				}
+			}
+		}
+		Ok(json)
+	}
+}
+
+impl AdvancementCondition {
+	fn to_result_condition(&self, scope: &str) -> ResultCondition {
+		let scope = scope.to_string();
+		match *self {
+			AdvancementCondition::Percent(level) => ResultCondition::Percent {
+				scope,
+				value: level as u64,
+			},
+			AdvancementCondition::Ranking(level) => ResultCondition::Ranking {
+				scope,
+				value: level as u64,
+			},
+			AdvancementCondition::AttemptResult(level) => ResultCondition::ResultAchieved {
+				scope,
+				value: Some(ResultValue::Ok(level)),
+			},
+		}
+	}
+
+	fn from_result_condition(condition: &ResultCondition) -> Option<AdvancementCondition> {
+		match condition {
+			ResultCondition::Percent { value, .. } => {
+				Some(AdvancementCondition::Percent(*value as usize))
+			}
+			ResultCondition::Ranking { value, .. } => {
+				Some(AdvancementCondition::Ranking(*value as usize))
+			}
+			ResultCondition::ResultAchieved {
+				value: Some(ResultValue::Ok(value)),
+				..
+			} => Some(AdvancementCondition::AttemptResult(*value)),
+			ResultCondition::ResultAchieved { .. } => None,
+		}
+	}
+}
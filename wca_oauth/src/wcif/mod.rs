@@ -1,11 +1,16 @@
 use serde::{Deserialize, Serialize};
 
 mod activity;
+mod activity_code;
 mod advancement_condition;
 mod assignment;
 mod avatar;
 mod cutoff;
+mod diff;
 mod event;
+mod event_id;
+mod extension;
+mod format_version;
 mod person;
 mod personal_best;
 mod qualification;
@@ -16,6 +21,7 @@ mod result_value;
 mod role;
 mod room;
 mod round;
+mod round_format;
 mod schedule;
 mod series;
 mod time_limit;
@@ -25,12 +31,19 @@ mod pariticipation_ruleset;
 mod scramble_set;
 
 pub use super::{Date, DateTime};
+#[cfg(feature = "tz")]
+pub use super::{LocalDateTime, Tz};
 pub use activity::*;
+pub use activity_code::*;
 pub use advancement_condition::*;
 pub use assignment::*;
 pub use avatar::*;
 pub use cutoff::*;
+pub use diff::*;
 pub use event::*;
+pub use event_id::*;
+pub use extension::*;
+pub use format_version::*;
 pub use person::*;
 pub use personal_best::*;
 pub use qualification::*;
@@ -41,6 +54,7 @@ pub use result_value::*;
 pub use role::*;
 pub use room::*;
 pub use round::*;
+pub use round_format::*;
 pub use schedule::*;
 pub use series::*;
 pub use time_limit::*;
@@ -51,12 +65,16 @@ pub use scramble_set::*;
 
 use crate::WcifContainer;
 
+/// Fields of a wcif object which are not modeled. They are kept when parsing and written back
+/// when serializing, so patching does not erase data written by the WCA or other tools.
+pub type OtherFields = serde_json::Map<String, serde_json::Value>;
+
 pub type WcifResult = std::result::Result<WcifContainer, WcifError>;
 
 #[derive(Deserialize, Serialize, Debug, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct Wcif {
-	pub format_version: String,
+	pub format_version: FormatVersion,
 	pub id: String,
 	pub name: String,
 	pub short_name: String,
@@ -67,6 +85,8 @@ pub struct Wcif {
 	pub registration_info: RegistrationInfo,
 	pub competitor_limit: Option<usize>,
 	pub extensions: Vec<serde_json::Value>,
+	#[serde(flatten)]
+	pub other: OtherFields,
 }
 
 #[derive(Deserialize, Serialize, Debug, PartialEq)]
@@ -76,6 +96,9 @@ pub struct WcifError {
 
 pub fn parse(json: String) -> WcifResult {
 	serde_json::from_str(&json)
-		.map(WcifContainer::new)
+		.map(|mut wcif: Wcif| {
+			wcif.upgrade();
+			WcifContainer::new(wcif)
+		})
 		.map_err(|_| serde_json::from_str(&json).unwrap())
 }
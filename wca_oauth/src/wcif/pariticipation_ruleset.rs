@@ -7,6 +7,8 @@ use super::*;
 pub struct ParticipationRuleset {
 	pub participation_source: Option<ParticipationSource>,
 	pub reserved_places: Option<ReservedPlaces>,
+	#[serde(flatten)]
+	pub other: OtherFields,
 }
 
 #[derive(Deserialize, Serialize, Debug, PartialEq, Clone)]
@@ -31,4 +33,6 @@ pub enum ParticipationSource {
 pub struct ReservedPlaces {
 	pub nationalities: Vec<String>,
 	pub count: u64,
+	#[serde(flatten)]
+	pub other: OtherFields,
 }
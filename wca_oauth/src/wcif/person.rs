@@ -11,12 +11,18 @@ pub struct Person {
 	pub wca_id: Option<WcaId>,
 	pub country_iso_2: String,
 	pub gender: char,
-	pub birthdate: serde_with::chrono::NaiveDate,
-	pub email: String,
+	/// Not included in the public wcif.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub birthdate: Option<Date>,
+	/// Not included in the public wcif.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub email: Option<String>,
 	pub avatar: Option<Avatar>,
 	pub roles: Vec<Role>,
 	pub registration: Option<Registration>,
 	pub assignments: Vec<Assignment>,
 	pub personal_bests: Vec<PersonalBest>,
 	pub extensions: Vec<serde_json::Value>,
+	#[serde(flatten)]
+	pub other: OtherFields,
 }
@@ -1,15 +1,18 @@
 use serde::{Deserialize, Serialize};
 
-use crate::ResultValue;
+use crate::{EventId, OtherFields, ResultValue};
 
 #[derive(Deserialize, Serialize, Debug, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct PersonalBest {
-	pub event_id: String,
+	pub event_id: EventId,
+	#[serde(rename = "best", alias = "value")]
 	pub value: ResultValue,
 	#[serde(rename = "type")]
 	pub t: String,
 	pub world_ranking: usize,
 	pub continental_ranking: usize,
 	pub national_ranking: usize,
+	#[serde(flatten)]
+	pub other: OtherFields,
 }
@@ -8,4 +8,6 @@ pub struct Qualification {
 	pub earliest_result_date: Option<Date>,
 	pub latest_result_date: Date,
 	pub result_condition: ResultCondition,
+	#[serde(flatten)]
+	pub other: OtherFields,
 }
@@ -1,13 +1,21 @@
 use serde::{Deserialize, Serialize};
 
+use crate::{EventId, OtherFields};
+
 #[derive(Deserialize, Serialize, Debug, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct Registration {
 	pub wca_registration_id: usize,
-	pub event_ids: Vec<String>,
+	pub event_ids: Vec<EventId>,
 	pub status: String,
-	pub guests: usize,
-	pub comments: String,
-	pub administrative_notes: String,
+	/// Not included in the public wcif, like `comments` and `administrative_notes`.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub guests: Option<usize>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub comments: Option<String>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub administrative_notes: Option<String>,
 	pub is_competing: bool,
+	#[serde(flatten)]
+	pub other: OtherFields,
 }
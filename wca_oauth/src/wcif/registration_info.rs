@@ -18,4 +18,6 @@ pub struct RegistrationInfo {
 	pub currency_code: String,
 	pub on_the_spot_registration: bool,
 	pub use_wca_registration: bool,
+	#[serde(flatten)]
+	pub other: OtherFields,
 }
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use crate::ResultValue;
+use crate::{OtherFields, ResultValue};
 
 #[derive(Deserialize, Serialize, Debug, PartialEq)]
 #[serde(rename_all = "camelCase")]
@@ -10,13 +10,18 @@ pub struct Result {
 	pub attempts: Vec<Attempt>,
 	pub best: ResultValue,
 	pub average: ResultValue,
+	#[serde(flatten)]
+	pub other: OtherFields,
 }
 
 #[derive(Deserialize, Serialize, Debug, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct Attempt {
+	#[serde(rename = "result", alias = "value")]
 	pub value: ResultValue,
 	pub reconstruction: Option<serde_json::Value>,
+	#[serde(flatten)]
+	pub other: OtherFields,
 }
 
 #[derive(Deserialize, Serialize, Debug, PartialEq, Clone)]
@@ -10,4 +10,6 @@ pub struct Room {
 	pub color: String,
 	pub activities: Vec<Activity>,
 	pub extensions: Vec<serde_json::Value>,
+	#[serde(flatten)]
+	pub other: OtherFields,
 }
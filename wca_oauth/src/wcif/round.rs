@@ -6,14 +6,19 @@ use super::*;
 #[serde(rename_all = "camelCase")]
 pub struct Round {
 	pub id: String,
+	/// Replaced by `participation_ruleset` in newer wcifs, which do not include it.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
 	pub linked_rounds: Option<Vec<String>>,
-	pub format: char,
+	pub format: RoundFormat,
 	pub time_limit: Option<TimeLimit>,
 	pub cutoff: Option<Cutoff>,
 	pub participation_ruleset: Option<ParticipationRuleset>,
 	pub results: Vec<Result>,
 	pub scramble_set_count: usize,
-	// pub scramble_sets: Vec<ScrambleSet>, // scramble_sets is not implemented by the wca
-	// currerntly.
+	/// Only included when the scrambles of the round have been uploaded.
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub scramble_sets: Vec<ScrambleSet>,
 	pub extensions: Vec<serde_json::Value>,
+	#[serde(flatten)]
+	pub other: OtherFields,
 }
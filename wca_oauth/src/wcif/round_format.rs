@@ -0,0 +1,145 @@
+use std::fmt::{Display, Formatter};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::Cutoff;
+
+/// The format of a round, stored in the wcif as a single character.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum RoundFormat {
+	BestOf1,
+	BestOf2,
+	BestOf3,
+	BestOf5,
+	/// Average of 5, with the best and worst attempt removed.
+	Average,
+	/// Mean of 3.
+	Mean,
+	HeadToHead,
+	Other(char),
+}
+
+/// What a round is ranked by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RankingType {
+	Single,
+	Average,
+	Mean,
+}
+
+impl RankingType {
+	/// The scope of a result condition ranking by this, i.e. `single` or `average`.
+	pub fn scope(&self) -> &'static str {
+		match self {
+			RankingType::Single => "single",
+			RankingType::Average | RankingType::Mean => "average",
+		}
+	}
+}
+
+impl RoundFormat {
+	pub fn id(&self) -> char {
+		match self {
+			RoundFormat::BestOf1 => '1',
+			RoundFormat::BestOf2 => '2',
+			RoundFormat::BestOf3 => '3',
+			RoundFormat::BestOf5 => '5',
+			RoundFormat::Average => 'a',
+			RoundFormat::Mean => 'm',
+			RoundFormat::HeadToHead => 'h',
+			RoundFormat::Other(id) => *id,
+		}
+	}
+
+	pub fn name(&self) -> &'static str {
+		match self {
+			RoundFormat::BestOf1 => "Best of 1",
+			RoundFormat::BestOf2 => "Best of 2",
+			RoundFormat::BestOf3 => "Best of 3",
+			RoundFormat::BestOf5 => "Best of 5",
+			RoundFormat::Average => "Average of 5",
+			RoundFormat::Mean => "Mean of 3",
+			RoundFormat::HeadToHead => "Head to head",
+			RoundFormat::Other(_) => "Unknown format",
+		}
+	}
+
+	/// The number of attempts of each competitor. `None` for head to head rounds, where it
+	/// depends on the matches, and for unknown formats.
+	pub fn attempts(&self) -> Option<usize> {
+		match self {
+			RoundFormat::BestOf1 => Some(1),
+			RoundFormat::BestOf2 => Some(2),
+			RoundFormat::BestOf3 | RoundFormat::Mean => Some(3),
+			RoundFormat::BestOf5 | RoundFormat::Average => Some(5),
+			RoundFormat::HeadToHead | RoundFormat::Other(_) => None,
+		}
+	}
+
+	pub fn ranking(&self) -> RankingType {
+		match self {
+			RoundFormat::Average => RankingType::Average,
+			RoundFormat::Mean => RankingType::Mean,
+			_ => RankingType::Single,
+		}
+	}
+
+	/// The number of attempts a cutoff may be placed after. Competitors who do not make the
+	/// cutoff in these attempts do not get the rest of the attempts of the format.
+	pub fn allowed_cutoff_attempts(&self) -> &'static [usize] {
+		match self {
+			RoundFormat::BestOf2 => &[1],
+			RoundFormat::BestOf3
+			| RoundFormat::BestOf5
+			| RoundFormat::Average
+			| RoundFormat::Mean => &[1, 2],
+			_ => &[],
+		}
+	}
+
+	pub fn allows_cutoff(&self, cutoff: &Cutoff) -> bool {
+		self.allowed_cutoff_attempts()
+			.contains(&cutoff.number_of_attempts)
+	}
+}
+
+impl From<char> for RoundFormat {
+	fn from(id: char) -> Self {
+		match id {
+			'1' => RoundFormat::BestOf1,
+			'2' => RoundFormat::BestOf2,
+			'3' => RoundFormat::BestOf3,
+			'5' => RoundFormat::BestOf5,
+			'a' => RoundFormat::Average,
+			'm' => RoundFormat::Mean,
+			'h' => RoundFormat::HeadToHead,
+			id => RoundFormat::Other(id),
+		}
+	}
+}
+
+/// Displays the name of the format, e.g. `Average of 5`.
+impl Display for RoundFormat {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		f.write_str(self.name())
+	}
+}
+
+impl<'de> Deserialize<'de> for RoundFormat {
+	fn deserialize<D>(deserializer: D) -> std::result::Result<RoundFormat, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		let id: char = Deserialize::deserialize(deserializer)?;
+		Ok(id.into())
+	}
+}
+
+impl Serialize for RoundFormat {
+	fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+	where
+		S: Serializer,
+	{
+		serializer.serialize_char(self.id())
+	}
+}
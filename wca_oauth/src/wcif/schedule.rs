@@ -8,4 +8,6 @@ pub struct Schedule {
 	pub start_date: Date,
 	pub number_of_days: usize,
 	pub venues: Vec<Venue>,
+	#[serde(flatten)]
+	pub other: OtherFields,
 }
@@ -1,9 +1,80 @@
+use std::fmt::{Display, Formatter};
+
 use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize, Serialize, Debug, PartialEq)]
+use crate::OtherFields;
+
+/// The scrambles used by one group of a round.
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct ScrambleSet {
-	id: u64,
-	scrambles: Vec<String>,
-	extra_scrambles: Vec<String>,
+	pub id: u64,
+	pub scrambles: Vec<Scramble>,
+	pub extra_scrambles: Vec<Scramble>,
+	#[serde(flatten)]
+	pub other: OtherFields,
+}
+
+impl ScrambleSet {
+	/// The scramble of an attempt, starting from 1.
+	pub fn scramble_for_attempt(&self, attempt: usize) -> Option<&Scramble> {
+		attempt
+			.checked_sub(1)
+			.and_then(|index| self.scrambles.get(index))
+	}
+}
+
+/// A single scramble. For multi-blind, one scramble holds the scrambles of all cubes of an
+/// attempt, one per line.
+#[derive(Clone, Deserialize, Serialize, Debug, PartialEq, Eq)]
+#[serde(transparent)]
+pub struct Scramble(pub String);
+
+impl Scramble {
+	pub fn as_str(&self) -> &str {
+		&self.0
+	}
+
+	/// The scrambles of the individual cubes of a multi-blind attempt. Any other scramble is a
+	/// single cube.
+	pub fn cubes(&self) -> impl Iterator<Item = &str> {
+		self.0
+			.lines()
+			.map(str::trim)
+			.filter(|line| !line.is_empty())
+	}
+
+	pub fn cube_count(&self) -> usize {
+		self.cubes().count()
+	}
+
+	/// The moves of the scramble. Fewest moves scrambles are required to start and end with
+	/// `R' U' F`, which is checked by `is_valid_fmc`.
+	pub fn moves(&self) -> impl Iterator<Item = &str> {
+		self.0.split_whitespace()
+	}
+
+	pub fn is_valid_fmc(&self) -> bool {
+		const PADDING: [&str; 3] = ["R'", "U'", "F"];
+		let moves: Vec<_> = self.moves().collect();
+		moves.len() >= 2 * PADDING.len() && moves.starts_with(&PADDING) && moves.ends_with(&PADDING)
+	}
+}
+
+impl From<String> for Scramble {
+	fn from(scramble: String) -> Self {
+		Scramble(scramble)
+	}
+}
+
+impl From<&str> for Scramble {
+	fn from(scramble: &str) -> Self {
+		Scramble(scramble.to_string())
+	}
+}
+
+impl Display for Scramble {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		f.write_str(&self.0)
+	}
 }
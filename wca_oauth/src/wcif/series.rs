@@ -1,9 +1,16 @@
 use serde::{Deserialize, Serialize};
 
+use crate::OtherFields;
+
 #[derive(Deserialize, Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
 pub struct Series {
 	pub id: String,
 	pub name: String,
+	#[serde(alias = "short_name")]
 	pub short_name: String,
+	#[serde(rename = "competitionIds", alias = "competitions")]
 	pub competitions: Vec<String>,
+	#[serde(flatten)]
+	pub other: OtherFields,
 }
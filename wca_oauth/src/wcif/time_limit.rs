@@ -1,8 +1,12 @@
 use serde::{Deserialize, Serialize};
 
+use crate::OtherFields;
+
 #[derive(Clone, Deserialize, Serialize, Debug, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct TimeLimit {
 	pub centiseconds: usize,
 	pub cumulative_round_ids: Vec<String>,
+	#[serde(flatten)]
+	pub other: OtherFields,
 }
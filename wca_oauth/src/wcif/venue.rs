@@ -1,3 +1,5 @@
+#[cfg(feature = "tz")]
+use chrono::TimeZone;
 use serde::{Deserialize, Serialize};
 
 use super::*;
@@ -13,4 +15,33 @@ pub struct Venue {
 	pub timezone: String,
 	pub rooms: Vec<Room>,
 	pub extensions: Vec<serde_json::Value>,
+	#[serde(flatten)]
+	pub other: OtherFields,
+}
+
+#[cfg(feature = "tz")]
+impl Venue {
+	/// The time zone of the venue, or `None` if `timezone` is not a known IANA time zone.
+	pub fn time_zone(&self) -> Option<Tz> {
+		self.timezone.parse().ok()
+	}
+
+	/// Converts a time of the wcif, which are all in UTC, to the local time of the venue. `None`
+	/// if the time zone of the venue is unknown.
+	pub fn local_time(&self, time: DateTime) -> Option<LocalDateTime> {
+		Some(self.time_zone()?.from_utc_datetime(&time))
+	}
+}
+
+/// Shows a time of the wcif in the time zone `timezone`, e.g. `2024-05-04 09:30:00 CEST`. If the
+/// time zone is unknown, or the `tz` feature is disabled, it is shown in UTC, e.g.
+/// `2024-05-04 07:30:00 UTC`.
+pub(crate) fn show_local_time(time: DateTime, timezone: &str) -> String {
+	#[cfg(feature = "tz")]
+	if let Ok(tz) = timezone.parse::<Tz>() {
+		return tz.from_utc_datetime(&time).to_string();
+	}
+	#[cfg(not(feature = "tz"))]
+	let _ = timezone;
+	format!("{time} UTC")
 }
@@ -0,0 +1,345 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+use crate::merge::{merge_json, round_conflicts};
+use crate::*;
+
+/// The top-level sections of a wcif which can be patched.
+const PATCHABLE_SECTIONS: [&str; 4] = ["persons", "events", "schedule", "extensions"];
+
+#[derive(Debug)]
+pub struct WcifContainer {
+	pub(crate) wcif: Wcif,
+	/// The wcif as it was when fetched or last patched. Used to only patch what changed.
+	original: Option<Value>,
+}
+
+impl WcifContainer {
+	/// Wraps a wcif and remembers it as the original, such that patching only sends what changes
+	/// from here on.
+	pub fn new(wcif: Wcif) -> WcifContainer {
+		let original = Some(to_json(&wcif));
+		WcifContainer { wcif, original }
+	}
+
+	/// Wraps a wcif without an original, such that the next patch sends every section.
+	pub fn without_original(wcif: Wcif) -> WcifContainer {
+		WcifContainer {
+			wcif,
+			original: None,
+		}
+	}
+
+	/// The body of a patch containing the sections which differ from the original. Persons are
+	/// sent individually, so only changed persons are included. Returns `None` if nothing changed.
+	pub fn changes(&self) -> Option<Value> {
+		let mut current = match to_json(&self.wcif) {
+			Value::Object(map) => map,
+			_ => unreachable!("a wcif serializes to an object"),
+		};
+		let original = self.original.as_ref().and_then(Value::as_object);
+		let mut changes = Map::new();
+		for section in PATCHABLE_SECTIONS {
+			let value = current.remove(section).unwrap_or(Value::Null);
+			let before = match original.and_then(|original| original.get(section)) {
+				Some(before) => before,
+				None => {
+					changes.insert(section.to_string(), value);
+					continue;
+				}
+			};
+			if section == "persons" {
+				let persons = changed_persons(before, value);
+				if !persons.is_empty() {
+					changes.insert(section.to_string(), Value::Array(persons));
+				}
+			} else if &value != before {
+				changes.insert(section.to_string(), value);
+			}
+		}
+		if changes.is_empty() {
+			None
+		} else {
+			Some(Value::Object(changes))
+		}
+	}
+
+	/// Applies the local changes on top of `remote`, a newer version of the wcif, which then
+	/// becomes the original. Without an original the local wcif is kept as it is. Fails without
+	/// changing anything if the changes conflict, or if together they do not give a valid wcif.
+	/// Groups of a round, or the assignments of a person in a round, changed on both sides are
+	/// always a conflict, so two concurrent grouping runs are never combined.
+	pub fn rebase(&mut self, remote: Wcif) -> std::result::Result<(), Vec<MergeConflict>> {
+		let base = match &self.original {
+			Some(base) => base,
+			None => return Ok(()),
+		};
+		let remote = to_json(&remote);
+		let local = to_json(&self.wcif);
+		let mut conflicts = round_conflicts(base, &local, &remote);
+		match merge_json(base, &local, &remote) {
+			Ok(wcif) if conflicts.is_empty() => self.wcif = wcif,
+			Ok(_) => return Err(conflicts),
+			Err(merge_conflicts) => {
+				conflicts.splice(0..0, merge_conflicts);
+				return Err(conflicts);
+			}
+		}
+		self.original = Some(remote);
+		Ok(())
+	}
+
+	/// Summary of what changed since the wcif was fetched or last patched, to be confirmed before
+	/// patching. `None` if there is no original.
+	pub fn change_summary(&self) -> serde_json::Result<Option<ChangeSummary>> {
+		let original: Wcif = match &self.original {
+			Some(original) => Wcif::deserialize(original)?,
+			None => return Ok(None),
+		};
+		Ok(Some(ChangeSummary::between(&original, &self.wcif)))
+	}
+
+	/// Makes the current state the original, e.g. after it has been patched.
+	pub fn mark_unchanged(&mut self) {
+		self.original = Some(to_json(&self.wcif));
+	}
+
+	pub fn get_mut<'a>(&'a mut self) -> &'a mut Wcif {
+		&mut self.wcif
+	}
+
+	pub fn get<'a>(&'a self) -> &'a Wcif {
+		&self.wcif
+	}
+
+	pub fn date(&self) -> Date {
+		self.wcif.schedule.start_date
+	}
+
+	pub fn events_iter(&self) -> impl Iterator<Item = &Event> {
+		self.wcif.events.iter()
+	}
+
+	pub fn events_iter_mut(&mut self) -> impl Iterator<Item = &mut Event> {
+		self.wcif.events.iter_mut()
+	}
+
+	pub fn patch_events(&mut self, func: impl FnMut(&mut Event)) {
+		self.events_iter_mut().for_each(func);
+	}
+
+	pub fn persons_iter(&self) -> impl Iterator<Item = &Person> {
+		self.wcif.persons.iter()
+	}
+
+	pub fn persons_iter_mut(&mut self) -> impl Iterator<Item = &mut Person> {
+		self.wcif.persons.iter_mut()
+	}
+
+	pub fn patch_persons(&mut self, func: impl FnMut(&mut Person)) {
+		self.persons_iter_mut().for_each(func);
+	}
+
+	pub fn round_iter(&self) -> impl Iterator<Item = &Round> {
+		self.events_iter().flat_map(|e| e.rounds.iter())
+	}
+
+	pub fn round_iter_mut(&mut self) -> impl Iterator<Item = &mut Round> {
+		self.events_iter_mut().flat_map(|e| e.rounds.iter_mut())
+	}
+
+	pub fn patch_rounds(&mut self, func: impl FnMut(&mut Round)) {
+		self.round_iter_mut().for_each(func);
+	}
+
+	pub fn activity_iter(&self) -> impl Iterator<Item = &Activity> {
+		self.wcif.schedule.venues.iter().flat_map(|venue| {
+			venue
+				.rooms
+				.iter()
+				.flat_map(|room| ActivityIter::new(&room.activities))
+		})
+	}
+
+	pub fn schedule_activity_iter(&self) -> impl Iterator<Item = &Activity> {
+		self.wcif
+			.schedule
+			.venues
+			.iter()
+			.flat_map(|venue| venue.rooms.iter().flat_map(|room| room.activities.iter()))
+	}
+
+	pub fn reg_ids_of_delegates(&self) -> Vec<usize> {
+		self.persons_iter()
+			.filter(|person| {
+				person.roles.contains(&Role::Delegate)
+					|| person.roles.contains(&Role::TraineeDelegate)
+			})
+			.filter_map(|person| person.registrant_id)
+			.collect()
+	}
+
+	pub fn overlapping_activities<'a>(&'a self) -> Vec<(&'a Activity, &'a Activity)> {
+		self.schedule_activity_iter()
+			.map(|act_1| {
+				self.schedule_activity_iter()
+					.filter(|act_2| act_1.overlaps(act_2))
+					.zip(std::iter::repeat(act_1))
+			})
+			.flatten()
+			.collect()
+	}
+
+	pub fn add_groups_to_event(
+		&mut self,
+		event: &EventId,
+		round: usize,
+		no: usize,
+		subgroups: usize,
+	) -> std::result::Result<&mut Vec<Activity>, ()> {
+		let round_code = ActivityCode::round(event.clone(), round);
+		let act = self
+			.wcif
+			.schedule
+			.venues
+			.iter_mut()
+			.flat_map(|v| &mut v.rooms)
+			.flat_map(|r| &mut r.activities)
+			.find(|a| round_code.contains(&a.activity_code))
+			.map(|a| {
+				if a.child_activities.len() != 0 {
+					let ids: Vec<_> = a.child_activities.iter().map(|a| a.id).collect();
+					for person in self.wcif.persons.iter_mut() {
+						person
+							.assignments
+							.retain(|act| !ids.contains(&act.activity_id));
+					}
+				}
+				a.child_activities = (0..no * subgroups)
+					.map(|g| {
+						let group_time = (a.end_time - a.start_time) / no as i32;
+						let start_time = a.start_time + (group_time * (g / subgroups) as i32);
+						let end_time = a.start_time + (group_time * ((g / subgroups) as i32 + 1));
+						Activity {
+							id: a.id * 1000 + g,
+							name: format!("{}, Group {}", a.name, g + 1),
+							activity_code: a.activity_code.clone().with_group(g + 1),
+							start_time,
+							end_time,
+							child_activities: vec![],
+							scramble_set_id: None,
+							extensions: vec![],
+							other: OtherFields::new(),
+						}
+					})
+					.collect();
+				a
+			});
+		match act {
+			Some(v) => Ok(&mut v.child_activities),
+			_ => Err(()),
+		}
+	}
+
+	/// Returns true if there exists groups for the round. Will panic if the event-round pair does not exist.
+	pub fn detect_round_groups_exist(&self, event: &EventId, round: usize) -> bool {
+		let round_code = ActivityCode::round(event.clone(), round);
+		let act = self
+			.wcif
+			.schedule
+			.venues
+			.iter()
+			.flat_map(|v| &v.rooms)
+			.flat_map(|r| &r.activities)
+			.find(|a| round_code.contains(&a.activity_code))
+			.map(|a| !a.child_activities.is_empty())
+			.expect("check that your event and round number is correct");
+		act
+	}
+
+	/// Returns the number of entered competitors and total number of competitors for a given
+	/// round. Will panic if the event-round pair does not exist.
+	pub fn count_entered(&self, event_id: &EventId, round_no: usize) -> (u64, u64) {
+		let round_id = ActivityCode::round(event_id.clone(), round_no).to_string();
+		let act = self
+			.wcif
+			.events
+			.iter()
+			.find(|event| event.id == *event_id)
+			.and_then(|event| event.rounds.iter().find(|round| round.id == round_id))
+			.map(|round| {
+				let entered = round
+					.results
+					.iter()
+					.filter(|res| res.ranking.is_some())
+					.count();
+				let competitors = round.results.len();
+				(entered as u64, competitors as u64)
+			})
+			.expect("check that your event and round number is correct");
+		act
+	}
+}
+
+pub(crate) struct ActivityIter<'a> {
+	activites: Vec<Box<dyn Iterator<Item = &'a Activity> + 'a>>,
+}
+
+impl<'a> ActivityIter<'a> {
+	pub(crate) fn new(vec: &'a [Activity]) -> Self {
+		ActivityIter {
+			activites: vec![Box::new(vec.iter())],
+		}
+	}
+}
+
+impl<'a> Iterator for ActivityIter<'a> {
+	type Item = &'a Activity;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if let Some(iter) = self.activites.last_mut() {
+			match iter.next() {
+				None => {
+					self.activites.pop();
+					self.next()
+				}
+				Some(v) => {
+					self.activites.push(Box::new(v.child_activities.iter()));
+					Some(v)
+				}
+			}
+		} else {
+			None
+		}
+	}
+}
+
+fn to_json(wcif: &Wcif) -> Value {
+	serde_json::to_value(wcif).expect("wcif is always serializable")
+}
+
+/// The persons of `after` which are new or differ from how they were in `before`. The api matches
+/// the persons of a patch by their wca user id.
+fn changed_persons(before: &Value, after: Value) -> Vec<Value> {
+	let before: HashMap<_, _> = before
+		.as_array()
+		.map(Vec::as_slice)
+		.unwrap_or_default()
+		.iter()
+		.map(|person| (wca_user_id(person), person))
+		.collect();
+	let after = match after {
+		Value::Array(persons) => persons,
+		_ => return Vec::new(),
+	};
+	after
+		.into_iter()
+		.filter(|person| before.get(&wca_user_id(person)) != Some(&person))
+		.collect()
+}
+
+fn wca_user_id(person: &Value) -> Option<u64> {
+	person.get("wcaUserId").and_then(Value::as_u64)
+}
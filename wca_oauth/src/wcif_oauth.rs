@@ -1,6 +1,6 @@
 use std::ops::{Deref, DerefMut};
 
-use crate::{OAuth, WcifContainer};
+use crate::{ApiResult, OAuth, Snapshot, SnapshotStore, WcaApiError, WcifContainer};
 
 pub struct WcifOAuth {
 	pub(crate) cont: WcifContainer,
@@ -22,11 +22,60 @@ impl DerefMut for WcifOAuth {
 }
 
 impl WcifOAuth {
-	pub async fn patch(&self) -> String {
+	pub async fn patch(&mut self) -> ApiResult<String> {
 		self.cont.patch(&self.oauth).await
 	}
 
+	pub async fn patch_with_reason(&mut self, reason: &str) -> ApiResult<String> {
+		self.cont.patch_with_reason(&self.oauth, reason).await
+	}
+
+	/// See `WcifContainer::restore`.
+	pub async fn restore(
+		&mut self,
+		store: &SnapshotStore,
+		snapshot: &Snapshot,
+	) -> ApiResult<String> {
+		self.cont.restore(store, snapshot, &self.oauth).await
+	}
+
 	pub fn disassemble(self) -> (WcifContainer, OAuth) {
 		(self.cont, self.oauth)
 	}
 }
+
+impl WcifContainer {
+	pub fn add_oauth(self, oauth: OAuth) -> WcifOAuth {
+		WcifOAuth { cont: self, oauth }
+	}
+
+	/// Patches the sections which changed since the wcif was fetched or last patched, see
+	/// `changes`. Returns an empty string without sending a request if nothing changed.
+	///
+	/// The wcif is fetched again first, and if someone else changed it in the meantime, the local
+	/// changes are merged into theirs with `rebase`. If they conflict nothing is patched and
+	/// `WcaApiError::Conflict` is returned.
+	pub async fn patch(&mut self, oauth: &OAuth) -> ApiResult<String> {
+		self.patch_with_reason(oauth, "patch").await
+	}
+
+	/// Same as `patch`. If the `OAuth` has a snapshot store, the remote wcif is saved to it first
+	/// with `reason`.
+	pub async fn patch_with_reason(&mut self, oauth: &OAuth, reason: &str) -> ApiResult<String> {
+		if self.changes().is_none() {
+			return Ok(String::new());
+		}
+		let remote = oauth.get_wcif(&self.wcif.id).await?;
+		if let Some(store) = oauth.snapshot_store() {
+			store.save(&remote.wcif, reason)?;
+		}
+		self.rebase(remote.wcif).map_err(WcaApiError::Conflict)?;
+		let changes = match self.changes() {
+			Some(changes) => changes,
+			None => return Ok(String::new()),
+		};
+		let response = oauth.patch_wcif(&changes, &self.wcif.id).await?;
+		self.mark_unchanged();
+		Ok(response)
+	}
+}
@@ -0,0 +1,95 @@
+mod common;
+
+use wca_oauth::{ActivityCode, EventId};
+
+#[test]
+fn parses_activity_codes() {
+	let code: ActivityCode = "333-r2-g3-a1".parse().unwrap();
+	assert_eq!(
+		code,
+		ActivityCode::round(EventId::E333, 2)
+			.with_group(3)
+			.with_attempt(1)
+	);
+	assert_eq!(code.event_id(), Some(&EventId::E333));
+	assert_eq!(code.round_number(), Some(2));
+	assert_eq!(code.group_number(), Some(3));
+	assert_eq!(code.attempt_number(), Some(1));
+	assert_eq!(code.round_id().as_deref(), Some("333-r2"));
+
+	let code: ActivityCode = "333fm-r1-a2".parse().unwrap();
+	assert_eq!(code.group_number(), None);
+	assert_eq!(code.attempt_number(), Some(2));
+
+	let code: ActivityCode = "other-lunch".parse().unwrap();
+	assert_eq!(code, ActivityCode::Other("lunch".to_string()));
+	assert_eq!(code.event_id(), None);
+	assert_eq!(code.round_id(), None);
+}
+
+#[test]
+fn rejects_invalid_activity_codes() {
+	for code in ["", "333-x1", "333-g1-r1", "333-r", "333-r1-r2", "-r1"] {
+		let error = code.parse::<ActivityCode>().unwrap_err();
+		assert_eq!(error.to_string(), format!("invalid activity code: {code}"));
+	}
+}
+
+#[test]
+fn keeps_codes_of_other_tools() {
+	for code in ["333-rx", "333-r01", "333-g1-r1", ""] {
+		let json = format!("\"{code}\"");
+		let parsed: ActivityCode = serde_json::from_str(&json).unwrap();
+		assert_eq!(parsed, ActivityCode::Unknown(code.to_string()));
+		assert_eq!(serde_json::to_string(&parsed).unwrap(), json);
+		assert_eq!(parsed.round_id(), None);
+	}
+	let parsed: ActivityCode = serde_json::from_str("\"333-r1-g2\"").unwrap();
+	assert_eq!(parsed, ActivityCode::round(EventId::E333, 1).with_group(2));
+
+	let unknown = ActivityCode::Unknown("333-r01".to_string());
+	assert!(unknown.contains(&unknown));
+	assert!(!ActivityCode::round(EventId::E333, 1).contains(&unknown));
+}
+
+#[test]
+fn displays_activity_codes() {
+	for code in ["333-r2-g3-a1", "333fm-r1-a2", "fto-r1", "sq1", "other-misc"] {
+		assert_eq!(code.parse::<ActivityCode>().unwrap().to_string(), code);
+	}
+	assert_eq!(
+		serde_json::to_string(&ActivityCode::round(EventId::Pyram, 1).with_group(2)).unwrap(),
+		"\"pyram-r1-g2\""
+	);
+}
+
+#[test]
+fn contains_only_its_own_activities() {
+	let round = ActivityCode::round(EventId::E333, 1);
+	let contains = |code: &str| round.contains(&code.parse().unwrap());
+	assert!(contains("333-r1"));
+	assert!(contains("333-r1-g2"));
+	assert!(!contains("333-r10"));
+	assert!(!contains("333-r10-g1"));
+	assert!(!contains("333oh-r1"));
+	assert!(!contains("333"));
+
+	let event = ActivityCode::event(EventId::E333);
+	assert!(event.contains(&round));
+	assert!(!event.contains(&"333oh-r1".parse().unwrap()));
+}
+
+#[test]
+fn groups_are_added_to_the_right_round() {
+	let mut wcif = common::competition();
+	assert!(wcif.detect_round_groups_exist(&EventId::E333, 1));
+	assert!(!wcif.detect_round_groups_exist(&EventId::E333, 2));
+
+	let groups = wcif.add_groups_to_event(&EventId::E333, 2, 2, 1).unwrap();
+	let codes: Vec<_> = groups
+		.iter()
+		.map(|group| group.activity_code.to_string())
+		.collect();
+	assert_eq!(codes, ["333-r2-g1", "333-r2-g2"]);
+	assert!(wcif.detect_round_groups_exist(&EventId::E333, 2));
+}
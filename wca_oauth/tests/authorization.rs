@@ -0,0 +1,132 @@
+#![cfg(feature = "client")]
+
+use std::time::{Duration, Instant};
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use wca_oauth::{AuthorizationRequest, OAuth, RedirectListener, WcaApiError};
+
+// Port 0 lets every test listen on a free port, as tests run in parallel.
+fn request() -> AuthorizationRequest {
+	AuthorizationRequest::new(
+		"client".to_string(),
+		"http://localhost:0/callback".to_string(),
+	)
+}
+
+fn port(listener: &RedirectListener) -> u16 {
+	listener.local_addr().unwrap().port()
+}
+
+/// Sends a GET request for `target` and returns the response.
+async fn get(port: u16, target: &str) -> String {
+	let mut stream = TcpStream::connect(("127.0.0.1", port)).await.unwrap();
+	let head = format!("GET {target} HTTP/1.1\r\nHost: localhost:{port}\r\n\r\n");
+	stream.write_all(head.as_bytes()).await.unwrap();
+	let mut response = String::new();
+	stream.read_to_string(&mut response).await.unwrap();
+	response
+}
+
+#[tokio::test]
+async fn receives_the_code_after_other_connections() {
+	let request = request();
+	let listener = request
+		.listen()
+		.await
+		.unwrap()
+		.read_timeout(Duration::from_millis(100));
+	let port = port(&listener);
+	let redirect = format!("/callback?code=abc&state={}", request.state());
+
+	let browser = async {
+		// A connection opened in advance and never used, which must not block the redirect.
+		let _idle = TcpStream::connect(("127.0.0.1", port)).await.unwrap();
+		let favicon = get(port, "/favicon.ico").await;
+		let redirect = get(port, &redirect).await;
+		(favicon, redirect)
+	};
+	let (code, (favicon, redirect)) = tokio::join!(listener.wait_for_code(), browser);
+	assert_eq!(code.unwrap(), "abc");
+	assert!(favicon.starts_with("HTTP/1.1 404 Not Found"));
+	assert!(redirect.starts_with("HTTP/1.1 200 OK"));
+	assert!(redirect.contains("Logged in."));
+}
+
+#[tokio::test]
+async fn rejects_redirects_with_another_state() {
+	let request = request();
+	let listener = request.listen().await.unwrap();
+	let port = port(&listener);
+	let (code, response) = tokio::join!(
+		listener.wait_for_code(),
+		get(port, "/callback?code=abc&state=forged")
+	);
+	match code {
+		Err(WcaApiError::Authorization(e)) => {
+			assert_eq!(e, "state of the redirect does not match the request")
+		}
+		other => panic!("expected an authorization error, got {other:?}"),
+	}
+	assert!(response.starts_with("HTTP/1.1 400 Bad Request"));
+}
+
+#[tokio::test]
+async fn reports_denied_access() {
+	let request = request();
+	let listener = request.listen().await.unwrap();
+	let port = port(&listener);
+	let (code, _) = tokio::join!(
+		listener.wait_for_code(),
+		get(
+			port,
+			"/callback?error=access_denied&error_description=The+user+said+no"
+		)
+	);
+	assert_eq!(
+		code.unwrap_err().to_string(),
+		"authorization failed: authorization was denied: The user said no"
+	);
+}
+
+#[tokio::test]
+async fn gives_up_after_the_timeout() {
+	let request = request();
+	let listener = request
+		.listen()
+		.await
+		.unwrap()
+		.timeout(Duration::from_millis(200));
+	let start = Instant::now();
+	let _idle = TcpStream::connect(("127.0.0.1", port(&listener)))
+		.await
+		.unwrap();
+	match listener.wait_for_code().await {
+		Err(WcaApiError::Authorization(e)) => {
+			assert_eq!(e, "no redirect was received within 200ms")
+		}
+		other => panic!("expected a timeout, got {other:?}"),
+	}
+	assert!(start.elapsed() < Duration::from_secs(5));
+}
+
+#[tokio::test]
+async fn invalid_auth_urls_are_reported() {
+	let request = request().auth_url("not a url");
+	assert!(matches!(request.url(), Err(WcaApiError::Authorization(_))));
+	match request.listen().await {
+		Err(WcaApiError::Authorization(e)) => assert!(e.starts_with("invalid auth url"), "{e}"),
+		other => panic!("expected an authorization error, got {other:?}"),
+	}
+
+	let builder = OAuth::builder(
+		"client".to_string(),
+		"http://localhost:0/callback".to_string(),
+	)
+	.auth_url("not a url".to_string());
+	let request = builder.authorization_request();
+	let result = builder
+		.authorize(&request, |url| panic!("{url} was opened"))
+		.await;
+	assert!(matches!(result, Err(WcaApiError::Authorization(_))));
+}
@@ -0,0 +1,83 @@
+#![cfg(feature = "blocking")]
+
+mod common;
+
+use common::{MockServer, Response};
+use wca_oauth::blocking::OAuth;
+use wca_oauth::{OAuthBuilder, SnapshotStore};
+
+/// A server handing out tokens for the authorization code, serving the wcif and accepting
+/// patches.
+fn server() -> MockServer {
+	MockServer::start(|request| match (request.method.as_str(), request.path()) {
+		(_, "/oauth/token") => common::tokens("access-1", "refresh-1", 7200),
+		("PATCH", _) => Response::ok("{}"),
+		_ => Response::ok(common::fixture("competition.json")),
+	})
+}
+
+fn builder(server: &MockServer) -> OAuthBuilder {
+	wca_oauth::OAuth::builder("client".to_string(), "http://localhost:8080/".to_string())
+		.client_secret("secret".to_string())
+		.auth_url(server.url())
+		.api_url(server.url())
+}
+
+#[test]
+fn fetches_and_patches_synchronously() {
+	let server = server();
+	let oauth = OAuth::get_auth_with(builder(&server), "code".to_string()).unwrap();
+	assert!(oauth.expires_at().is_some());
+
+	let mut wcif = oauth.get_wcif("TestOpen2024").unwrap();
+	assert_eq!(wcif.get().id, "TestOpen2024");
+	wcif.get_mut().schedule.venues[0].name = "Patched Hall".to_string();
+	wcif.patch_blocking(&oauth).unwrap();
+	assert!(wcif.changes().is_none());
+
+	let requests = server.requests();
+	let patch = requests
+		.iter()
+		.find(|request| request.method == "PATCH")
+		.unwrap();
+	assert_eq!(patch.path(), "/competitions/TestOpen2024/wcif");
+	assert_eq!(patch.header("authorization"), Some("Bearer access-1"));
+	let body: serde_json::Value = serde_json::from_str(&patch.body).unwrap();
+	assert_eq!(body["schedule"]["venues"][0]["name"], "Patched Hall");
+	assert!(body.get("persons").is_none());
+}
+
+#[test]
+fn sessions_are_saved_and_loaded_synchronously() {
+	let server = server();
+	let path = common::temp_dir("blocking_sessions").join("session.json");
+	let oauth = OAuth::get_auth_with(builder(&server), "code".to_string()).unwrap();
+	oauth.save(&path).unwrap();
+
+	let store = SnapshotStore::new(common::temp_dir("blocking_sessions_snapshots"));
+	let loaded = OAuth::load_private_with(
+		wca_oauth::OAuth::builder(String::new(), String::new()).snapshot_store(store.clone()),
+		&path,
+	)
+	.unwrap();
+	assert_eq!(loaded.as_async().api_url(), server.url());
+
+	let mut wcif = loaded
+		.get_wcif("TestOpen2024")
+		.unwrap()
+		.add_blocking_oauth(loaded);
+	wcif.get_mut().schedule.venues[0].name = "Patched Hall".to_string();
+	wcif.patch_with_reason("rename the venue").unwrap();
+
+	let snapshots = store.list("TestOpen2024").unwrap();
+	assert_eq!(snapshots.len(), 1);
+	assert_eq!(snapshots[0].reason, "rename the venue");
+	assert_eq!(
+		server
+			.requests()
+			.iter()
+			.filter(|request| request.method == "PATCH")
+			.count(),
+		1
+	);
+}
@@ -0,0 +1,100 @@
+mod common;
+
+use wca_oauth::{Assignment, AssignmentCode, ChangeSummary, EventId, OtherFields, WcifContainer};
+
+#[test]
+fn no_changes() {
+	let wcif = common::competition();
+	let summary = wcif.change_summary().unwrap().unwrap();
+	assert!(summary.is_empty());
+	assert_eq!(summary.to_string(), "No changes.\n");
+}
+
+#[test]
+fn needs_an_original() {
+	let wcif = WcifContainer::without_original(common::competition_wcif());
+	assert_eq!(wcif.change_summary().unwrap(), None);
+}
+
+#[test]
+fn groups_and_assignments() {
+	let mut wcif = common::competition();
+	wcif.add_groups_to_event(&EventId::E333, 2, 2, 1).unwrap();
+	wcif.get_mut().persons[2].assignments.push(Assignment {
+		activity_id: 6000,
+		assignment_code: AssignmentCode::Competitor,
+		station_number: Some(3),
+		other: OtherFields::new(),
+	});
+	// Replacing the groups of the first round removes the assignments to them.
+	wcif.add_groups_to_event(&EventId::E333, 1, 1, 1).unwrap();
+	wcif.round_iter_mut()
+		.find(|round| round.id == "333-r2")
+		.unwrap()
+		.scramble_set_count = 2;
+
+	let summary = wcif.change_summary().unwrap().unwrap();
+	let codes = |activities: &[wca_oauth::ActivitySummary]| {
+		activities
+			.iter()
+			.map(|activity| activity.activity_code.to_string())
+			.collect::<Vec<_>>()
+	};
+	assert_eq!(
+		codes(&summary.activities_added),
+		["333-r1-g1", "333-r2-g1", "333-r2-g2"]
+	);
+	assert_eq!(
+		codes(&summary.activities_removed),
+		["333-r1-g1", "333-r1-g2"]
+	);
+	assert_eq!(summary.scramble_set_counts.len(), 1);
+	assert_eq!(summary.scramble_set_counts[0].round_id, "333-r2");
+	assert_eq!(summary.scramble_set_counts[0].before, 1);
+	assert_eq!(summary.scramble_set_counts[0].after, 2);
+
+	let names: Vec<_> = summary
+		.assignments
+		.iter()
+		.map(|person| person.name.as_str())
+		.collect();
+	assert_eq!(names, ["Alice Example", "Bob Example", "Carol Example"]);
+	assert!(summary.assignments[0].added.is_empty());
+	assert_eq!(summary.assignments[0].removed.len(), 2);
+	assert_eq!(summary.assignments[2].added.len(), 1);
+	assert_eq!(summary.assignments[2].added[0].activity_id, 6000);
+
+	assert_eq!(
+		summary.to_string(),
+		"\
++ activity 333-r1-g1 (3x3x3 Cube, Round 1, Group 1)
++ activity 333-r2-g1 (3x3x3 Cube, Round 2, Group 1)
++ activity 333-r2-g2 (3x3x3 Cube, Round 2, Group 2)
+- activity 333-r1-g1 (3x3x3 Cube, Round 1, Group 1)
+- activity 333-r1-g2 (3x3x3 Cube, Round 1, Group 2)
+~ 333-r2 scramble sets: 1 -> 2
+Alice Example:
+  - competitor in 333-r1-g1 at station 4
+  - staff-judge in 333-r1-g2
+Bob Example:
+  - competitor in 333-r1-g2 at station 1
+Carol Example:
+  + competitor in 333-r2-g1 at station 3
+"
+	);
+}
+
+#[test]
+fn summarizes_between_any_two_wcifs() {
+	let before = common::competition_wcif();
+	let mut after = common::competition_wcif();
+	after.persons[1].assignments.clear();
+	let summary = ChangeSummary::between(&before, &after);
+	assert_eq!(summary.assignments.len(), 1);
+	assert_eq!(summary.assignments[0].wca_user_id, 102);
+	assert_eq!(
+		summary.assignments[0].removed[0].activity_code,
+		Some("333-r1-g2".parse().unwrap())
+	);
+	assert!(ChangeSummary::between(&after, &after).is_empty());
+}
@@ -0,0 +1,65 @@
+mod common;
+
+use serde_json::Value;
+use wca_oauth::{Assignment, AssignmentCode, OtherFields, Person};
+
+fn user_ids(changes: &Value) -> Vec<u64> {
+	changes["persons"]
+		.as_array()
+		.unwrap()
+		.iter()
+		.map(|person| person["wcaUserId"].as_u64().unwrap())
+		.collect()
+}
+
+#[test]
+fn only_changed_sections_are_patched() {
+	let mut wcif = common::competition();
+	assert_eq!(wcif.changes(), None);
+
+	wcif.get_mut().schedule.venues[0].name = "Main Hall".to_string();
+	let changes = wcif.changes().unwrap();
+	let sections: Vec<_> = changes.as_object().unwrap().keys().collect();
+	assert_eq!(sections, ["schedule"]);
+	assert_eq!(changes["schedule"]["venues"][0]["name"], "Main Hall");
+}
+
+#[test]
+fn only_changed_persons_are_patched() {
+	let mut wcif = common::competition();
+	let bob = wcif.get().persons[1].wca_user_id;
+	wcif.patch_persons(|person| {
+		if person.wca_user_id == bob {
+			person.assignments.push(Assignment {
+				activity_id: 3,
+				assignment_code: AssignmentCode::Judge,
+				station_number: None,
+				other: OtherFields::new(),
+			});
+		}
+	});
+	let changes = wcif.changes().unwrap();
+	let sections: Vec<_> = changes.as_object().unwrap().keys().collect();
+	assert_eq!(sections, ["persons"]);
+	assert_eq!(user_ids(&changes), [bob as u64]);
+}
+
+#[test]
+fn reordered_persons_are_unchanged() {
+	let mut wcif = common::competition();
+	wcif.get_mut().persons.reverse();
+	assert_eq!(wcif.changes(), None);
+}
+
+#[test]
+fn new_persons_are_patched() {
+	let mut json: Value = serde_json::from_str(&common::fixture("competition.json")).unwrap();
+	let carol = json["persons"].as_array_mut().unwrap().pop().unwrap();
+	let mut wcif = wca_oauth::parse(json.to_string()).unwrap();
+	let carol: Person = serde_json::from_value(carol).unwrap();
+	let id = carol.wca_user_id;
+	wcif.get_mut().persons.insert(0, carol);
+
+	let changes = wcif.changes().unwrap();
+	assert_eq!(user_ids(&changes), [id as u64]);
+}
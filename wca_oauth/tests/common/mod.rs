@@ -0,0 +1,222 @@
+// Every test crate includes this module, but none uses all of it.
+#![allow(dead_code)]
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+
+use wca_oauth::{Wcif, WcifContainer};
+
+/// The contents of a file in `tests/fixtures`.
+pub fn fixture(name: &str) -> String {
+	std::fs::read_to_string(format!("tests/fixtures/{name}")).unwrap()
+}
+
+pub fn wcif(name: &str) -> WcifContainer {
+	wca_oauth::parse(fixture(name)).unwrap()
+}
+
+/// A small competition in Copenhagen with 3x3x3 and its groups scheduled.
+pub fn competition() -> WcifContainer {
+	wcif("competition.json")
+}
+
+/// The competition fixture as a plain wcif, e.g. to be rebased onto.
+pub fn competition_wcif() -> Wcif {
+	serde_json::from_str(&fixture("competition.json")).unwrap()
+}
+
+/// A request received by a `MockServer`.
+#[derive(Debug, Clone)]
+pub struct Request {
+	pub method: String,
+	/// The path including the query, e.g. `/competitions?page=2`.
+	pub target: String,
+	pub headers: Vec<(String, String)>,
+	pub body: String,
+}
+
+impl Request {
+	pub fn header(&self, name: &str) -> Option<&str> {
+		self.headers
+			.iter()
+			.find(|(n, _)| n.eq_ignore_ascii_case(name))
+			.map(|(_, value)| value.as_str())
+	}
+
+	pub fn path(&self) -> &str {
+		self.target.split('?').next().unwrap()
+	}
+
+	/// The decoded query parameters.
+	pub fn query(&self) -> Vec<(String, String)> {
+		let query = self.target.split_once('?').map(|(_, q)| q).unwrap_or("");
+		url::form_urlencoded::parse(query.as_bytes())
+			.into_owned()
+			.collect()
+	}
+
+	/// The decoded parameters of a form body, such as the one sent to `/oauth/token`.
+	pub fn form(&self) -> Vec<(String, String)> {
+		url::form_urlencoded::parse(self.body.as_bytes())
+			.into_owned()
+			.collect()
+	}
+
+	pub fn form_param(&self, name: &str) -> Option<String> {
+		self.form()
+			.into_iter()
+			.find(|(n, _)| n == name)
+			.map(|(_, value)| value)
+	}
+}
+
+#[derive(Debug, Clone)]
+pub struct Response {
+	pub status: u16,
+	pub headers: Vec<(String, String)>,
+	pub body: String,
+}
+
+impl Response {
+	pub fn new(status: u16, body: impl Into<String>) -> Response {
+		Response {
+			status,
+			headers: vec![],
+			body: body.into(),
+		}
+	}
+
+	pub fn ok(body: impl Into<String>) -> Response {
+		Response::new(200, body)
+	}
+
+	pub fn header(mut self, name: &str, value: impl Into<String>) -> Response {
+		self.headers.push((name.to_string(), value.into()));
+		self
+	}
+}
+
+/// A http server on a random local port standing in for the WCA. Every request is answered by
+/// the handler and recorded. Connections are closed after every response.
+pub struct MockServer {
+	url: String,
+	requests: Arc<Mutex<Vec<Request>>>,
+}
+
+impl MockServer {
+	pub fn start(mut handler: impl FnMut(&Request) -> Response + Send + 'static) -> MockServer {
+		let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+		let url = format!("http://{}", listener.local_addr().unwrap());
+		let requests = Arc::new(Mutex::new(Vec::new()));
+		let recorded = Arc::clone(&requests);
+		std::thread::spawn(move || {
+			for stream in listener.incoming() {
+				respond(stream.unwrap(), &mut handler, &recorded);
+			}
+		});
+		MockServer { url, requests }
+	}
+
+	/// Same as `start`, but every connection is answered on its own thread, so requests can be
+	/// in flight at the same time.
+	pub fn start_concurrent(
+		handler: impl Fn(&Request) -> Response + Send + Sync + 'static,
+	) -> MockServer {
+		let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+		let url = format!("http://{}", listener.local_addr().unwrap());
+		let requests = Arc::new(Mutex::new(Vec::new()));
+		let recorded = Arc::clone(&requests);
+		let handler = Arc::new(handler);
+		std::thread::spawn(move || {
+			for stream in listener.incoming() {
+				let stream = stream.unwrap();
+				let handler = Arc::clone(&handler);
+				let recorded = Arc::clone(&recorded);
+				std::thread::spawn(move || respond(stream, &mut |r| handler(r), &recorded));
+			}
+		});
+		MockServer { url, requests }
+	}
+
+	pub fn url(&self) -> &str {
+		&self.url
+	}
+
+	pub fn requests(&self) -> Vec<Request> {
+		self.requests.lock().unwrap().clone()
+	}
+}
+
+fn respond(
+	mut stream: TcpStream,
+	handler: &mut dyn FnMut(&Request) -> Response,
+	recorded: &Mutex<Vec<Request>>,
+) {
+	let request = match read_request(&mut stream) {
+		Some(request) => request,
+		None => return,
+	};
+	let response = handler(&request);
+	recorded.lock().unwrap().push(request);
+	let mut head = format!(
+		"HTTP/1.1 {} Mock\r\ncontent-length: {}\r\nconnection: close\r\n",
+		response.status,
+		response.body.len()
+	);
+	for (name, value) in &response.headers {
+		head.push_str(&format!("{name}: {value}\r\n"));
+	}
+	head.push_str("\r\n");
+	let _ = stream.write_all(head.as_bytes());
+	let _ = stream.write_all(response.body.as_bytes());
+}
+
+fn read_request(stream: &mut TcpStream) -> Option<Request> {
+	let mut reader = BufReader::new(stream);
+	let mut line = String::new();
+	reader.read_line(&mut line).ok()?;
+	let mut parts = line.split_whitespace();
+	let method = parts.next()?.to_string();
+	let target = parts.next()?.to_string();
+
+	let mut headers = vec![];
+	loop {
+		let mut line = String::new();
+		reader.read_line(&mut line).ok()?;
+		let line = line.trim_end();
+		if line.is_empty() {
+			break;
+		}
+		let (name, value) = line.split_once(':')?;
+		headers.push((name.trim().to_string(), value.trim().to_string()));
+	}
+	let length = headers
+		.iter()
+		.find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+		.map(|(_, value)| value.parse().unwrap())
+		.unwrap_or(0);
+	let mut body = vec![0; length];
+	reader.read_exact(&mut body).ok()?;
+	Some(Request {
+		method,
+		target,
+		headers,
+		body: String::from_utf8(body).unwrap(),
+	})
+}
+
+/// An empty directory for the test named `name`, removed first if an earlier run left it.
+pub fn temp_dir(name: &str) -> std::path::PathBuf {
+	let dir = std::env::temp_dir().join(format!("wca_oauth-{name}-{}", std::process::id()));
+	let _ = std::fs::remove_dir_all(&dir);
+	std::fs::create_dir_all(&dir).unwrap();
+	dir
+}
+
+/// The answer of `/oauth/token` handing out the given tokens.
+pub fn tokens(access_token: &str, refresh_token: &str, expires_in: u64) -> Response {
+	Response::ok(format!(
+		r#"{{"access_token":"{access_token}","token_type":"Bearer","expires_in":{expires_in},"refresh_token":"{refresh_token}","scope":"public manage_competitions"}}"#
+	))
+}
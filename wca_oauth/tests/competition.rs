@@ -0,0 +1,86 @@
+mod common;
+
+use wca_oauth::{Competition, Date, DateTime};
+
+/// The competition served by `/competitions/{id}`, with a delegate with an avatar, a trainee
+/// delegate and an organizer without a WCA id, both with the default avatar.
+fn competition() -> Competition {
+	let json = format!("[{}]", common::fixture("competition_api.json"));
+	Competition::from_json(&json).unwrap().pop().unwrap()
+}
+
+fn date(date: &str) -> Date {
+	date.parse().unwrap()
+}
+
+fn date_time(date_time: &str) -> DateTime {
+	date_time.parse().unwrap()
+}
+
+#[test]
+fn delegates_and_organizers_are_parsed() {
+	let competition = competition();
+	let names: Vec<_> = competition
+		.delegates
+		.iter()
+		.map(|delegate| delegate.name.as_str())
+		.collect();
+	assert_eq!(names, ["Dana Delegate", "Erik Trainee"]);
+
+	let delegate = &competition.delegates[0];
+	assert_eq!(delegate.id, 8123);
+	assert_eq!(
+		serde_json::to_value(&delegate.wca_id).unwrap(),
+		"2014DELE01"
+	);
+	let avatar = delegate.avatar.as_ref().unwrap();
+	assert_eq!(
+		avatar.url,
+		"https://avatars.worldcubeassociation.org/uploads/user/avatar/2014DELE01/1580000000.jpg"
+	);
+	assert_eq!(
+		avatar.thumb_url,
+		"https://avatars.worldcubeassociation.org/uploads/user/avatar/2014DELE01/1580000000_thumb.jpg"
+	);
+	assert_eq!(avatar.other["is_default"], false);
+
+	let organizer = &competition.organizers[0];
+	assert_eq!(organizer.id, 20311);
+	assert_eq!(organizer.wca_id, None);
+	assert_eq!(organizer.avatar.as_ref().unwrap().other["is_default"], true);
+}
+
+#[test]
+fn competition_days() {
+	// The competition is on the 4th and 5th of May.
+	let competition = competition();
+	let days = [
+		("2024-05-03", true, false),
+		("2024-05-04", false, true),
+		("2024-05-05", false, true),
+		("2024-05-06", false, false),
+	];
+	for (day, upcoming, ongoing) in days {
+		assert_eq!(competition.is_upcoming(date(day)), upcoming, "{day}");
+		assert_eq!(competition.is_ongoing(date(day)), ongoing, "{day}");
+	}
+}
+
+#[test]
+fn registration_opens_and_closes() {
+	// Registration is open from the 1st of March until the 30th of April, 18:00 UTC.
+	let competition = competition();
+	let times = [
+		("2024-03-01T17:59:59", false),
+		("2024-03-01T18:00:00", true),
+		("2024-04-30T17:59:59", true),
+		("2024-04-30T18:00:00", false),
+	];
+	for (now, open) in times {
+		assert_eq!(
+			competition.registration_is_open(date_time(now)),
+			open,
+			"{now}"
+		);
+	}
+}
@@ -0,0 +1,105 @@
+#![cfg(feature = "client")]
+
+mod common;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use common::{MockServer, Response};
+use wca_oauth::{Competition, Dashboard, OAuth, RetryPolicy, WcaApiError};
+
+fn competition(id: &str) -> Competition {
+	let mut competition: Competition =
+		serde_json::from_str(&common::fixture("competition_api.json")).unwrap();
+	competition.id = id.to_string();
+	competition
+}
+
+/// Serves the wcif of every competition except `Broken2024`, which fails, and records the
+/// highest number of requests in flight at the same time.
+fn server(max_in_flight: Arc<AtomicUsize>) -> MockServer {
+	let in_flight = AtomicUsize::new(0);
+	MockServer::start_concurrent(move |request| {
+		let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+		max_in_flight.fetch_max(now, Ordering::SeqCst);
+		std::thread::sleep(Duration::from_millis(50));
+		in_flight.fetch_sub(1, Ordering::SeqCst);
+		if request.path().starts_with("/competitions/Broken2024/") {
+			Response::new(500, "Internal Server Error")
+		} else {
+			Response::ok(common::fixture("competition.json"))
+		}
+	})
+}
+
+#[tokio::test]
+async fn failures_are_kept_per_competition() {
+	let max_in_flight = Arc::new(AtomicUsize::new(0));
+	let server = server(Arc::clone(&max_in_flight));
+	let oauth = OAuth::builder(
+		"client".to_string(),
+		"urn:ietf:wg:oauth:2.0:oob".to_string(),
+	)
+	.api_url(server.url())
+	.retry_policy(RetryPolicy::none())
+	.implicit("token".to_string());
+	let ids = [
+		"Open2024",
+		"Broken2024",
+		"Autumn2024",
+		"Winter2024",
+		"Spring2025",
+	];
+	let competitions = ids.iter().map(|id| competition(id)).collect();
+
+	let dashboard = Dashboard::fetch(&oauth, competitions, 2).await;
+
+	assert_eq!(server.requests().len(), ids.len());
+	assert_eq!(max_in_flight.load(Ordering::SeqCst), 2);
+
+	// Every competition has an entry, in the order they were given.
+	let order: Vec<_> = dashboard
+		.iter()
+		.map(|(competition, _)| competition.id())
+		.collect();
+	assert_eq!(order, ids);
+	for id in ids {
+		assert!(dashboard.wcif(id).is_some(), "no entry for {id}");
+	}
+
+	let failures: Vec<_> = dashboard
+		.failures()
+		.map(|(competition, error)| (competition.id(), error))
+		.collect();
+	assert_eq!(failures.len(), 1);
+	assert_eq!(failures[0].0, "Broken2024");
+	assert!(
+		matches!(failures[0].1, WcaApiError::Http { status, .. } if status.as_u16() == 500),
+		"{:?}",
+		failures[0].1
+	);
+	assert_eq!(dashboard.iter().filter(|(_, wcif)| wcif.is_ok()).count(), 4);
+
+	let wcifs = dashboard.into_wcifs();
+	assert_eq!(wcifs.len(), ids.len());
+}
+
+#[tokio::test]
+async fn at_least_one_request_is_sent_at_a_time() {
+	let max_in_flight = Arc::new(AtomicUsize::new(0));
+	let server = server(Arc::clone(&max_in_flight));
+	let oauth = OAuth::builder(
+		"client".to_string(),
+		"urn:ietf:wg:oauth:2.0:oob".to_string(),
+	)
+	.api_url(server.url())
+	.implicit("token".to_string());
+	let competitions = vec![competition("Open2024"), competition("Autumn2024")];
+
+	let wcifs = oauth.get_wcifs(&competitions, 0).await;
+
+	assert_eq!(wcifs.len(), 2);
+	assert!(wcifs.values().all(Result::is_ok));
+	assert_eq!(max_in_flight.load(Ordering::SeqCst), 1);
+}
@@ -0,0 +1,245 @@
+mod common;
+
+use serde_json::{json, Value};
+use wca_oauth::{diff, AssignmentCode, ResultValue, Wcif};
+
+fn competition_json() -> Value {
+	serde_json::from_str(&common::fixture("competition.json")).unwrap()
+}
+
+fn wcif(json: Value) -> Wcif {
+	serde_json::from_value(json).unwrap()
+}
+
+fn activity(id: usize, activity_code: &str, start_time: &str, end_time: &str) -> Value {
+	json!({
+		"id": id,
+		"name": activity_code,
+		"activityCode": activity_code,
+		"startTime": start_time,
+		"endTime": end_time,
+		"childActivities": [],
+		"extensions": [],
+	})
+}
+
+#[test]
+fn no_changes() {
+	let diff = diff(common::competition().get(), common::competition().get());
+	assert!(diff.is_empty());
+	assert_eq!(diff.to_string(), "No changes.\n");
+}
+
+#[test]
+fn persons_and_registration_status() {
+	let mut json = competition_json();
+	let persons = json["persons"].as_array_mut().unwrap();
+	let mut dave = persons[2].clone();
+	dave["wcaUserId"] = json!(104);
+	dave["registrantId"] = json!(4);
+	dave["name"] = json!("Dave Example");
+	persons.push(dave);
+	persons.remove(1);
+	persons[0]["registration"]["status"] = json!("deleted");
+
+	let diff = diff(common::competition().get(), &wcif(json));
+	let names = |persons: &[wca_oauth::PersonSummary]| {
+		persons
+			.iter()
+			.map(|person| person.name.clone())
+			.collect::<Vec<_>>()
+	};
+	assert_eq!(names(&diff.persons_added), ["Dave Example"]);
+	assert_eq!(names(&diff.persons_removed), ["Bob Example"]);
+	assert_eq!(diff.registration_status.len(), 1);
+	let change = &diff.registration_status[0];
+	assert_eq!(change.person.wca_user_id, 101);
+	assert_eq!(change.before.as_deref(), Some("accepted"));
+	assert_eq!(change.after.as_deref(), Some("deleted"));
+}
+
+#[test]
+fn activities_added_removed_and_shifted() {
+	let mut json = competition_json();
+	let activities = json["schedule"]["venues"][0]["rooms"][0]["activities"]
+		.as_array_mut()
+		.unwrap();
+	activities.retain(|activity| activity["id"] != 5);
+	activities.push(activity(
+		7,
+		"other-lunch",
+		"2024-05-04T09:30:00Z",
+		"2024-05-04T10:00:00Z",
+	));
+	activities[2]["startTime"] = json!("2024-05-04T10:30:00Z");
+	activities[2]["endTime"] = json!("2024-05-04T11:00:00Z");
+
+	let diff = diff(common::competition().get(), &wcif(json));
+	let ids = |activities: &[wca_oauth::ActivityDiff]| {
+		activities
+			.iter()
+			.map(|activity| activity.id)
+			.collect::<Vec<_>>()
+	};
+	assert_eq!(ids(&diff.activities_added), [7]);
+	assert_eq!(ids(&diff.activities_removed), [5]);
+	assert_eq!(diff.activities_removed[0].room, "Main Room");
+	assert_eq!(diff.time_shifts.len(), 1);
+	let shift = &diff.time_shifts[0];
+	assert_eq!(shift.id, 6);
+	assert_eq!(shift.activity_code.to_string(), "333-r2");
+	assert_eq!(shift.timezone, "Europe/Copenhagen");
+	assert_eq!(shift.before.0.to_string(), "2024-05-04 10:00:00");
+	assert_eq!(shift.after.0.to_string(), "2024-05-04 10:30:00");
+}
+
+#[test]
+fn repeated_activity_codes_are_told_apart() {
+	let mut json = competition_json();
+	let activities = json["schedule"]["venues"][0]["rooms"][0]["activities"]
+		.as_array_mut()
+		.unwrap();
+	activities.push(activity(
+		7,
+		"other-lunch",
+		"2024-05-04T09:30:00Z",
+		"2024-05-04T10:00:00Z",
+	));
+	activities.push(activity(
+		8,
+		"other-lunch",
+		"2024-05-05T09:30:00Z",
+		"2024-05-05T10:00:00Z",
+	));
+	let before = wcif(json.clone());
+
+	let activities = json["schedule"]["venues"][0]["rooms"][0]["activities"]
+		.as_array_mut()
+		.unwrap();
+	activities.retain(|activity| activity["id"] != 7);
+	activities.last_mut().unwrap()["startTime"] = json!("2024-05-05T09:00:00Z");
+	activities.push(activity(
+		9,
+		"other-lunch",
+		"2024-05-06T09:30:00Z",
+		"2024-05-06T10:00:00Z",
+	));
+
+	let diff = diff(&before, &wcif(json));
+	assert_eq!(diff.activities_added.len(), 1);
+	assert_eq!(diff.activities_added[0].id, 9);
+	assert_eq!(diff.activities_removed.len(), 1);
+	assert_eq!(diff.activities_removed[0].id, 7);
+	assert_eq!(diff.time_shifts.len(), 1);
+	assert_eq!(diff.time_shifts[0].id, 8);
+}
+
+#[test]
+fn assignments() {
+	let mut json = competition_json();
+	let persons = json["persons"].as_array_mut().unwrap();
+	persons[0]["assignments"].as_array_mut().unwrap().remove(1);
+	persons[2]["assignments"] = json!([
+		{ "activityId": 3, "assignmentCode": "competitor", "stationNumber": 5 },
+		{ "activityId": 42, "assignmentCode": "staff-judge", "stationNumber": null },
+	]);
+
+	let diff = diff(common::competition().get(), &wcif(json));
+	assert_eq!(diff.assignments.len(), 2);
+	let alice = &diff.assignments[0];
+	assert_eq!(alice.name, "Alice Example");
+	assert!(alice.added.is_empty());
+	assert_eq!(alice.removed.len(), 1);
+	assert_eq!(alice.removed[0].activity_id, 4);
+	assert_eq!(alice.removed[0].assignment_code, AssignmentCode::Judge);
+
+	let carol = &diff.assignments[1];
+	assert_eq!(carol.registrant_id, Some(3));
+	assert_eq!(carol.added.len(), 2);
+	assert_eq!(
+		carol.added[0].activity_code.as_ref().unwrap().to_string(),
+		"333-r1-g1"
+	);
+	assert_eq!(carol.added[0].station_number, Some(5));
+	assert_eq!(carol.added[1].activity_code, None);
+}
+
+#[test]
+fn results() {
+	let mut json = competition_json();
+	let rounds = json["events"][0]["rounds"].as_array_mut().unwrap();
+	let results = rounds[0]["results"].as_array_mut().unwrap();
+	results[0]["best"] = json!(850);
+	let bob = results.remove(1);
+	rounds[1]["results"] = json!([bob]);
+
+	let diff = diff(common::competition().get(), &wcif(json));
+	let changes: Vec<_> = diff
+		.results
+		.iter()
+		.map(|change| {
+			(
+				change.round_id.as_str(),
+				change.person_id,
+				change.before.as_ref().map(|result| result.best),
+				change.after.as_ref().map(|result| result.best),
+			)
+		})
+		.collect();
+	assert_eq!(
+		changes,
+		[
+			(
+				"333-r1",
+				1,
+				Some(ResultValue::Ok(900)),
+				Some(ResultValue::Ok(850))
+			),
+			("333-r1", 2, Some(ResultValue::Ok(2000)), None),
+			("333-r2", 2, None, Some(ResultValue::Ok(2000))),
+		]
+	);
+}
+
+#[test]
+#[cfg(feature = "tz")]
+fn text_report() {
+	let mut json = competition_json();
+	let persons = json["persons"].as_array_mut().unwrap();
+	persons.remove(1);
+	persons[1]["registration"]["status"] = json!("accepted");
+	persons[1]["assignments"] =
+		json!([{ "activityId": 3, "assignmentCode": "competitor", "stationNumber": 5 }]);
+	let activities = json["schedule"]["venues"][0]["rooms"][0]["activities"]
+		.as_array_mut()
+		.unwrap();
+	activities.push(activity(
+		7,
+		"other-lunch",
+		"2024-05-04T09:30:00Z",
+		"2024-05-04T10:00:00Z",
+	));
+	activities[3]["endTime"] = json!("2024-05-04T11:00:00Z");
+	json["events"][0]["rounds"][0]["results"][0]["ranking"] = json!(2);
+
+	let report = diff(common::competition().get(), &wcif(json)).to_string();
+	assert_eq!(
+		report,
+		"\
+Persons:
+  - Bob Example (registrant 2)
+Registration status:
+  Carol Example (registrant 3): pending -> accepted
+Activities:
+  + other-lunch (other-lunch) in Main Room, 2024-05-04 11:30:00 CEST - 2024-05-04 12:00:00 CEST
+Schedule:
+  333-r2 in Main Room: 2024-05-04 12:00:00 CEST - 2024-05-04 12:30:00 CEST -> 2024-05-04 12:00:00 CEST - 2024-05-04 13:00:00 CEST
+Assignments:
+  Carol Example:
+    + competitor in 333-r1-g1 at station 5
+Results:
+  333-r1:
+    ~ registrant 1: best 900, average 1017, ranked 1 -> best 900, average 1017, ranked 2
+"
+	);
+}
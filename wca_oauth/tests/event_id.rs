@@ -0,0 +1,50 @@
+mod common;
+
+use wca_oauth::{EventId, RoundFormat};
+
+#[test]
+fn parses_official_and_unofficial_events() {
+	for event in EventId::OFFICIAL {
+		assert!(event.is_official());
+		assert_eq!(EventId::from(event.as_str()), event);
+	}
+	let fto: EventId = serde_json::from_str("\"fto\"").unwrap();
+	assert_eq!(fto, EventId::Other("fto".to_string()));
+	assert!(!fto.is_official());
+	assert_eq!(fto.name(), "fto");
+	assert_eq!(
+		serde_json::to_string(&EventId::E333bf).unwrap(),
+		"\"333bf\""
+	);
+}
+
+#[test]
+fn event_metadata() {
+	assert_eq!(EventId::Sq1.name(), "Square-1");
+	assert_eq!(EventId::E333.default_attempts(), 5);
+	assert_eq!(EventId::E666.default_attempts(), 3);
+	assert_eq!(EventId::E444bf.default_attempts(), 3);
+	assert_eq!(
+		EventId::E333fm.allowed_formats(),
+		[
+			RoundFormat::Mean,
+			RoundFormat::BestOf2,
+			RoundFormat::BestOf1
+		]
+	);
+	assert!(EventId::E333fm.is_counted_in_moves());
+	assert!(EventId::E333mbf.is_multi_blind());
+	assert!(!EventId::E333mbf.is_timed());
+	assert!(EventId::Clock.is_timed());
+}
+
+#[test]
+fn wcif_uses_event_ids() {
+	let wcif = common::competition();
+	let events: Vec<_> = wcif.events_iter().map(|event| &event.id).collect();
+	assert_eq!(events, [&EventId::E333, &EventId::E333bf]);
+	assert!(wcif.persons_iter().any(|person| person
+		.registration
+		.as_ref()
+		.is_some_and(|registration| registration.event_ids.contains(&EventId::E333))));
+}
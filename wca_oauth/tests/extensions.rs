@@ -0,0 +1,119 @@
+mod common;
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use wca_oauth::{Extensible, WcifContainer};
+
+#[derive(Deserialize, Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct ActivityConfig {
+	capacity: f64,
+	groups: usize,
+	scramblers: usize,
+	runners: usize,
+	assign_judges: bool,
+}
+
+const ACTIVITY_CONFIG: &str = "groupifier.ActivityConfig";
+const ACTIVITY_CONFIG_URL: &str =
+	"https://groupifier.jonatanklosko.com/wcif-extensions/ActivityConfig.json";
+
+fn third_party() -> WcifContainer {
+	common::wcif("third_party.json")
+}
+
+#[test]
+fn reads_extensions() {
+	let wcif = third_party();
+	let activity = wcif
+		.schedule_activity_iter()
+		.flat_map(|activity| &activity.child_activities)
+		.find(|activity| !activity.extensions.is_empty())
+		.unwrap();
+	assert_eq!(
+		activity
+			.get_extension::<ActivityConfig>(ACTIVITY_CONFIG)
+			.unwrap(),
+		Some(ActivityConfig {
+			capacity: 0.5,
+			groups: 2,
+			scramblers: 1,
+			runners: 1,
+			assign_judges: true,
+		})
+	);
+	assert_eq!(
+		activity.extension(ACTIVITY_CONFIG).unwrap().spec_url,
+		ACTIVITY_CONFIG_URL
+	);
+	assert_eq!(
+		activity
+			.get_extension::<ActivityConfig>("groupifier.RoomConfig")
+			.unwrap(),
+		None
+	);
+
+	let venue = &wcif.get().schedule.venues[0];
+	assert!(venue
+		.get_extension::<ActivityConfig>("groupifier.VenueConfig")
+		.is_err());
+	assert_eq!(
+		wcif.get()
+			.get_extension::<serde_json::Value>("delegateDashboard.Competition")
+			.unwrap(),
+		Some(json!({ "lastSync": "2024-05-01" }))
+	);
+}
+
+#[test]
+fn writes_extensions() {
+	let mut wcif = third_party();
+	let person = &mut wcif.get_mut().persons[1];
+	let extensions = person.extensions.len();
+
+	person
+		.set_extension(
+			"competitionGroups.Person",
+			"https://example.com/Person.json",
+			json!({ "tags": [] }),
+		)
+		.unwrap();
+	assert_eq!(person.extensions.len(), extensions);
+	assert_eq!(person.extensions[1]["data"], json!({ "tags": [] }));
+
+	let config = ActivityConfig {
+		capacity: 1.0,
+		groups: 3,
+		scramblers: 2,
+		runners: 2,
+		assign_judges: false,
+	};
+	let round = &mut wcif.get_mut().events[0].rounds[0];
+	round
+		.set_extension(ACTIVITY_CONFIG, ACTIVITY_CONFIG_URL, &config)
+		.unwrap();
+	assert_eq!(round.get_extension(ACTIVITY_CONFIG).unwrap(), Some(config));
+	assert_eq!(
+		round.extensions.last().unwrap(),
+		&json!({
+			"id": ACTIVITY_CONFIG,
+			"specUrl": ACTIVITY_CONFIG_URL,
+			"data": {
+				"capacity": 1.0,
+				"groups": 3,
+				"scramblers": 2,
+				"runners": 2,
+				"assignJudges": false,
+			},
+		})
+	);
+
+	assert!(round.remove_extension(ACTIVITY_CONFIG));
+	assert!(!round.remove_extension(ACTIVITY_CONFIG));
+	assert_eq!(
+		round
+			.get_extension::<ActivityConfig>(ACTIVITY_CONFIG)
+			.unwrap(),
+		None
+	);
+}
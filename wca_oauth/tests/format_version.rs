@@ -0,0 +1,90 @@
+mod common;
+
+use serde_json::Value;
+use wca_oauth::{FormatVersion, ParticipationRuleset, ParticipationSource, ResultCondition, Wcif};
+
+#[test]
+fn legacy_advancement_conditions_are_upgraded() {
+	let wcif = wca_oauth::parse(common::fixture("legacy_1_0.json")).unwrap();
+	let wcif = wcif.get();
+	assert_eq!(wcif.format_version, FormatVersion::LATEST);
+
+	let rounds = &wcif.events[0].rounds;
+	assert_eq!(
+		rounds[0].participation_ruleset,
+		Some(ParticipationRuleset {
+			participation_source: Some(ParticipationSource::Registrations),
+			reserved_places: None,
+			other: Default::default(),
+		})
+	);
+	assert_eq!(
+		rounds[1]
+			.participation_ruleset
+			.as_ref()
+			.and_then(|ruleset| ruleset.participation_source.clone()),
+		Some(ParticipationSource::Round {
+			round_id: "333-r1".to_string(),
+			result_condition: ResultCondition::Percent {
+				scope: "average".to_string(),
+				value: 75,
+			},
+		})
+	);
+	assert!(rounds
+		.iter()
+		.all(|round| !round.other.contains_key("advancementCondition")));
+}
+
+#[test]
+fn serializes_to_legacy_version() {
+	let json = common::fixture("legacy_1_0.json");
+	let wcif = wca_oauth::parse(json.clone()).unwrap();
+	let legacy = wcif.get().to_json_version(&FormatVersion::V1_0).unwrap();
+	let original: Value = serde_json::from_str(&json).unwrap();
+
+	assert_eq!(legacy["formatVersion"], "1.0");
+	for (event, original_event) in legacy["events"]
+		.as_array()
+		.unwrap()
+		.iter()
+		.zip(original["events"].as_array().unwrap())
+	{
+		for (round, original_round) in event["rounds"]
+			.as_array()
+			.unwrap()
+			.iter()
+			.zip(original_round_list(original_event))
+		{
+			assert_eq!(
+				round["advancementCondition"],
+				original_round["advancementCondition"]
+			);
+			assert!(round.get("participationRuleset").is_none());
+		}
+	}
+}
+
+fn original_round_list(event: &Value) -> &Vec<Value> {
+	event["rounds"].as_array().unwrap()
+}
+
+#[test]
+fn current_version_is_not_changed() {
+	let json = common::fixture("competition.json");
+	let mut wcif: Wcif = serde_json::from_str(&json).unwrap();
+	let before = serde_json::to_value(&wcif).unwrap();
+	wcif.upgrade();
+	assert_eq!(serde_json::to_value(&wcif).unwrap(), before);
+	assert_eq!(
+		wcif.to_json_version(&FormatVersion::LATEST).unwrap(),
+		before
+	);
+}
+
+#[test]
+fn unknown_versions_are_kept() {
+	let version: FormatVersion = serde_json::from_str("\"3.0\"").unwrap();
+	assert_eq!(version, FormatVersion::Other("3.0".to_string()));
+	assert_eq!(serde_json::to_string(&version).unwrap(), "\"3.0\"");
+}
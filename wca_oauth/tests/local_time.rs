@@ -0,0 +1,97 @@
+#![cfg(feature = "tz")]
+
+mod common;
+
+use wca_oauth::{diff, Date, DateTime, Time, Tz};
+
+fn utc(date: &str, time: &str) -> DateTime {
+	DateTime::new(date.parse::<Date>().unwrap(), time.parse::<Time>().unwrap())
+}
+
+#[test]
+fn activities_have_local_times() {
+	let wcif = common::competition();
+	let venue = &wcif.get().schedule.venues[0];
+	assert_eq!(venue.time_zone(), Some(Tz::Europe__Copenhagen));
+
+	let scheduled: Vec<_> = wcif.scheduled_activities().collect();
+	assert_eq!(scheduled.len(), 4);
+	assert_eq!(
+		scheduled[1].local_start().unwrap().to_string(),
+		"2024-05-04 09:30:00 CEST"
+	);
+	assert_eq!(
+		scheduled[1].activity.local_end(venue).unwrap().to_string(),
+		"2024-05-04 10:30:00 CEST"
+	);
+	assert_eq!(
+		scheduled[1].to_string(),
+		"3x3x3 Cube, Round 1 in Main Room, 2024-05-04 09:30:00 CEST - 2024-05-04 10:30:00 CEST"
+	);
+
+	let group = wcif.scheduled_activity(4).unwrap();
+	assert_eq!(group.room.name, "Main Room");
+	assert_eq!(group.activity.activity_code.to_string(), "333-r1-g2");
+	assert_eq!(wcif.all_scheduled_activities().count(), 6);
+}
+
+#[test]
+fn local_times_follow_daylight_saving_time() {
+	let mut wcif = common::competition();
+	let venue = &mut wcif.get_mut().schedule.venues[0];
+	let activity = &mut venue.rooms[0].activities[0];
+	activity.start_time = utc("2024-03-31", "00:30:00");
+	activity.end_time = utc("2024-03-31", "01:30:00");
+	let activity = &venue.rooms[0].activities[0];
+	assert_eq!(
+		activity.local_start(venue).unwrap().to_string(),
+		"2024-03-31 01:30:00 CET"
+	);
+	assert_eq!(
+		activity.local_end(venue).unwrap().to_string(),
+		"2024-03-31 03:30:00 CEST"
+	);
+
+	let activity = &mut venue.rooms[0].activities[0];
+	activity.start_time = utc("2024-10-27", "00:30:00");
+	activity.end_time = utc("2024-10-27", "01:30:00");
+	let activity = &venue.rooms[0].activities[0];
+	assert_eq!(
+		activity.local_start(venue).unwrap().to_string(),
+		"2024-10-27 02:30:00 CEST"
+	);
+	assert_eq!(
+		activity.local_end(venue).unwrap().to_string(),
+		"2024-10-27 02:30:00 CET"
+	);
+}
+
+#[test]
+fn unknown_time_zones_have_no_local_times() {
+	let mut wcif = common::competition();
+	let venue = &mut wcif.get_mut().schedule.venues[0];
+	venue.timezone = "Europe/Atlantis".to_string();
+	assert_eq!(venue.time_zone(), None);
+	assert_eq!(venue.rooms[0].activities[1].local_start(venue), None);
+
+	// Reports fall back to UTC, and say so.
+	assert_eq!(
+		wcif.scheduled_activity(2).unwrap().to_string(),
+		"3x3x3 Cube, Round 1 in Main Room, 2024-05-04 07:30:00 UTC - 2024-05-04 08:30:00 UTC"
+	);
+}
+
+#[test]
+fn reports_show_local_times() {
+	let before = common::competition();
+	let mut after = common::competition();
+	let activity = &mut after.get_mut().schedule.venues[0].rooms[0].activities[3];
+	activity.start_time = utc("2024-05-04", "11:00:00");
+	activity.end_time = utc("2024-05-04", "11:30:00");
+
+	let report = diff(before.get(), after.get()).to_string();
+	assert!(report.contains(
+		"333-r2 in Main Room: 2024-05-04 12:00:00 CEST - 2024-05-04 12:30:00 CEST -> \
+		 2024-05-04 13:00:00 CEST - 2024-05-04 13:30:00 CEST"
+	));
+}
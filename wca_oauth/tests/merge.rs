@@ -0,0 +1,187 @@
+mod common;
+
+use wca_oauth::{
+	Activity, Assignment, AssignmentCode, DateTime, EventId, OtherFields, Wcif, WcifContainer,
+};
+
+fn assignment(activity_id: usize, station_number: Option<usize>) -> Assignment {
+	Assignment {
+		activity_id,
+		assignment_code: AssignmentCode::Competitor,
+		station_number,
+		other: OtherFields::new(),
+	}
+}
+
+fn activity(id: usize, activity_code: &str, name: &str) -> Activity {
+	let time = |time: &str| format!("2024-05-04T{time}").parse::<DateTime>().unwrap();
+	Activity {
+		id,
+		name: name.to_string(),
+		activity_code: activity_code.parse().unwrap(),
+		start_time: time("11:00:00"),
+		end_time: time("12:00:00"),
+		child_activities: vec![],
+		scramble_set_id: None,
+		extensions: vec![],
+		other: OtherFields::new(),
+	}
+}
+
+/// A copy of the wcif in `container`.
+fn plain(container: &WcifContainer) -> Wcif {
+	serde_json::from_value(serde_json::to_value(container.get()).unwrap()).unwrap()
+}
+
+fn activity_ids(activities: &[Activity]) -> Vec<usize> {
+	activities.iter().map(|activity| activity.id).collect()
+}
+
+#[test]
+fn merges_changes_to_different_sections() {
+	let mut local = common::competition();
+	local.get_mut().persons[2]
+		.assignments
+		.push(assignment(3, Some(5)));
+	let mut remote = common::competition_wcif();
+	remote.events[0].rounds[1].scramble_set_count = 3;
+
+	local.rebase(remote).unwrap();
+	assert_eq!(local.get().persons[2].assignments, [assignment(3, Some(5))]);
+	assert_eq!(local.get().events[0].rounds[1].scramble_set_count, 3);
+
+	// The remote wcif is the new original, so only the local changes are left to patch.
+	let changes = local.changes().unwrap();
+	assert_eq!(changes.as_object().unwrap().len(), 1);
+	assert_eq!(changes["persons"].as_array().unwrap().len(), 1);
+	assert_eq!(changes["persons"][0]["wcaUserId"], 103);
+}
+
+#[test]
+fn merges_concurrent_assignment_edits() {
+	let mut local = common::competition();
+	local.get_mut().persons[0]
+		.assignments
+		.push(assignment(5, None));
+	let mut remote = common::competition_wcif();
+	remote.persons[0].assignments.push(assignment(6, None));
+	remote.persons[1].assignments[0].station_number = Some(2);
+
+	local.rebase(remote).unwrap();
+	let alice: Vec<_> = local.get().persons[0]
+		.assignments
+		.iter()
+		.map(|assignment| assignment.activity_id)
+		.collect();
+	assert_eq!(alice, [3, 4, 6, 5]);
+	assert_eq!(
+		local.get().persons[1].assignments[0].station_number,
+		Some(2)
+	);
+}
+
+#[test]
+fn merges_insertions_into_the_same_list() {
+	let mut local = common::competition();
+	let room = &mut local.get_mut().schedule.venues[0].rooms[0];
+	room.activities.push(activity(7, "other-lunch", "Lunch"));
+	room.activities[1].child_activities.push(activity(
+		9,
+		"333-r1-g3",
+		"3x3x3 Cube, Round 1, Group 3",
+	));
+	let mut remote = common::competition_wcif();
+	let room = &mut remote.schedule.venues[0].rooms[0];
+	room.activities.push(activity(8, "other-awards", "Awards"));
+	room.activities[3].child_activities.push(activity(
+		10,
+		"333-r2-g1",
+		"3x3x3 Cube, Round 2, Group 1",
+	));
+
+	local.rebase(remote).unwrap();
+	let room = &local.get().schedule.venues[0].rooms[0];
+	assert_eq!(activity_ids(&room.activities), [1, 2, 5, 6, 8, 7]);
+	assert_eq!(
+		activity_ids(&room.activities[1].child_activities),
+		[3, 4, 9]
+	);
+	assert_eq!(activity_ids(&room.activities[3].child_activities), [10]);
+}
+
+#[test]
+fn concurrent_grouping_runs_conflict() {
+	let mut local = common::competition();
+	local.add_groups_to_event(&EventId::E333, 2, 2, 1).unwrap();
+	local.get_mut().persons[0]
+		.assignments
+		.push(assignment(6000, None));
+	let mut remote = common::competition();
+	remote.add_groups_to_event(&EventId::E333, 2, 2, 1).unwrap();
+	remote.get_mut().persons[0]
+		.assignments
+		.push(assignment(6001, None));
+
+	let conflicts = local.rebase(plain(&remote)).unwrap_err();
+	assert_eq!(conflicts.len(), 1);
+	assert_eq!(conflicts[0].path, "persons/101/assignments/333-r2");
+	assert_eq!(conflicts[0].base, None);
+	assert_eq!(conflicts[0].local.as_ref().unwrap()[0]["activityId"], 6000);
+	assert_eq!(conflicts[0].remote.as_ref().unwrap()[0]["activityId"], 6001);
+	assert_eq!(
+		local.get().persons[0]
+			.assignments
+			.last()
+			.unwrap()
+			.activity_id,
+		6000
+	);
+
+	// Different groups for the same round conflict, whatever the assignments.
+	let mut remote = common::competition();
+	remote.add_groups_to_event(&EventId::E333, 2, 3, 1).unwrap();
+	let conflicts = local.rebase(plain(&remote)).unwrap_err();
+	let paths: Vec<_> = conflicts.iter().map(|c| c.path.as_str()).collect();
+	assert!(paths.contains(&"schedule/333-r2/childActivities"));
+}
+
+#[test]
+fn conflicting_changes_are_not_merged() {
+	let mut local = common::competition();
+	local.get_mut().schedule.venues[0].name = "Local Hall".to_string();
+	local.get_mut().persons[0].assignments[0].station_number = Some(7);
+	local.get_mut().persons[2].name = "Carol Local".to_string();
+	let mut remote = common::competition_wcif();
+	remote.schedule.venues[0].name = "Remote Hall".to_string();
+	remote.persons[0].assignments[0].station_number = Some(8);
+	remote.persons.remove(2);
+
+	let conflicts = local.rebase(remote).unwrap_err();
+	let paths: Vec<_> = conflicts
+		.iter()
+		.map(|conflict| conflict.path.as_str())
+		.collect();
+	assert_eq!(
+		paths,
+		[
+			"persons/101/assignments/3:competitor/stationNumber",
+			"persons/103",
+			"schedule/venues/1/name",
+			"persons/101/assignments/333-r1",
+		]
+	);
+	assert_eq!(conflicts[1].remote, None);
+	assert_eq!(
+		conflicts[2].to_string(),
+		"schedule/venues/1/name was changed to \"Local Hall\" locally and to \"Remote Hall\" \
+		 remotely"
+	);
+
+	// Nothing is changed, so the conflicts can be resolved locally and rebased again.
+	assert_eq!(local.get().schedule.venues[0].name, "Local Hall");
+	assert_eq!(
+		local.get().persons[0].assignments[0].station_number,
+		Some(7)
+	);
+	assert!(local.changes().unwrap().get("events").is_none());
+}
@@ -0,0 +1,158 @@
+#![cfg(feature = "client")]
+
+mod common;
+
+use common::{MockServer, Request, Response};
+use futures::TryStreamExt;
+use wca_oauth::{CompetitionFilter, Date, OAuth, PublicClient};
+
+fn competition(id: &str) -> serde_json::Value {
+	serde_json::json!({
+		"id": id,
+		"name": id,
+		"registration_open": "2024-03-01T18:00:00.000Z",
+		"registration_close": "2024-04-30T18:00:00.000Z",
+		"announced_at": "2024-02-20T10:00:00.000Z",
+		"start_date": "2024-05-04",
+		"end_date": "2024-05-05",
+		"competitor_limit": 100,
+		"cancelled_at": null,
+		"url": null,
+		"website": null,
+		"short_name": id,
+		"city": "Copenhagen",
+		"venue_address": null,
+		"venue_details": null,
+		"latitude_degrees": 55.67,
+		"longitude_degrees": 12.56,
+		"country_iso2": "DK",
+		"event_ids": ["333"],
+		"delegates": [],
+		"organizers": [],
+	})
+}
+
+/// Serves `pages` of competitions, linking each page to the next with a `Link` header like the
+/// WCA does. Pages are numbered from 1, the first one is requested without a number.
+fn server(pages: Vec<Vec<&'static str>>) -> MockServer {
+	MockServer::start(move |request| {
+		let page = request
+			.query()
+			.into_iter()
+			.find(|(name, _)| name == "page")
+			.map(|(_, page)| page.parse().unwrap())
+			.unwrap_or(1);
+		let items: Vec<_> = pages[page - 1].iter().map(|id| competition(id)).collect();
+		let link = |page: usize, rel: &str| {
+			let host = request.header("host").unwrap();
+			format!("<http://{host}/competitions?page={page}&per_page=2>; rel=\"{rel}\"")
+		};
+		let mut links = vec![link(pages.len(), "last")];
+		if page < pages.len() {
+			links.insert(0, link(page + 1, "next"));
+		}
+		Response::ok(serde_json::to_string(&items).unwrap()).header("Link", links.join(", "))
+	})
+}
+
+fn ids(competitions: &[wca_oauth::Competition]) -> Vec<&str> {
+	competitions.iter().map(|c| c.id()).collect()
+}
+
+fn date(date: &str) -> Date {
+	date.parse().unwrap()
+}
+
+/// The query of `request` without the page parameters.
+fn filters(request: &Request) -> Vec<(String, String)> {
+	let mut query = request.query();
+	query.retain(|(name, _)| name != "page" && name != "per_page");
+	query.sort();
+	query
+}
+
+fn params(params: &[(&str, &str)]) -> Vec<(String, String)> {
+	params
+		.iter()
+		.map(|(name, value)| (name.to_string(), value.to_string()))
+		.collect()
+}
+
+#[tokio::test]
+async fn managed_competitions_follow_every_page_with_the_filter() {
+	let server = server(vec![
+		vec!["A2024", "B2024"],
+		vec!["C2024", "D2024"],
+		vec!["E2024"],
+	]);
+	let oauth = OAuth::builder("client".to_string(), "http://localhost:8080/".to_string())
+		.api_url(server.url())
+		.implicit("token".to_string());
+	let filter = CompetitionFilter::new()
+		.between(date("2024-01-01"), date("2024-12-31"))
+		.country("DK")
+		.include_cancelled();
+	let competitions = oauth
+		.get_competitions_managed_by_me_filtered(&filter)
+		.await
+		.unwrap();
+	assert_eq!(
+		ids(&competitions),
+		["A2024", "B2024", "C2024", "D2024", "E2024"]
+	);
+
+	let requests = server.requests();
+	let targets: Vec<_> = requests.iter().map(|r| r.target.as_str()).collect();
+	assert_eq!(
+		targets[1..],
+		[
+			"/competitions?page=2&per_page=2",
+			"/competitions?page=3&per_page=2"
+		]
+	);
+	assert_eq!(
+		filters(&requests[0]),
+		params(&[
+			("country_iso2", "DK"),
+			("end", "2024-12-31"),
+			("include_cancelled", "true"),
+			("managed_by_me", "true"),
+			("start", "2024-01-01"),
+		])
+	);
+	assert!(requests
+		.iter()
+		.all(|r| r.header("authorization") == Some("Bearer token")));
+}
+
+#[tokio::test]
+async fn pages_are_only_fetched_when_needed() {
+	let server = server(vec![vec!["A2024", "B2024"], vec!["C2024"]]);
+	let client = PublicClient::with_api_url(server.url());
+	let filter = CompetitionFilter::new().upcoming(date("2024-05-01"));
+	let stream = client.search_competitions_stream("Open", &filter);
+	futures::pin_mut!(stream);
+	assert_eq!(stream.try_next().await.unwrap().unwrap().id(), "A2024");
+	assert_eq!(stream.try_next().await.unwrap().unwrap().id(), "B2024");
+	assert_eq!(server.requests().len(), 1);
+	assert_eq!(
+		filters(&server.requests()[0]),
+		params(&[("ongoing_and_future", "2024-05-01"), ("q", "Open")])
+	);
+
+	assert_eq!(stream.try_next().await.unwrap().unwrap().id(), "C2024");
+	assert!(stream.try_next().await.unwrap().is_none());
+	assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn an_empty_page_ends_the_listing() {
+	let server = server(vec![vec!["A2024"], vec![], vec!["C2024"]]);
+	let client = PublicClient::with_api_url(server.url());
+	let competitions = client
+		.search_competitions("", &CompetitionFilter::new())
+		.await
+		.unwrap();
+	assert_eq!(ids(&competitions), ["A2024"]);
+	assert_eq!(server.requests().len(), 2);
+}
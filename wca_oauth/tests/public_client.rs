@@ -0,0 +1,128 @@
+#![cfg(feature = "client")]
+
+mod common;
+
+use common::{MockServer, Response};
+use wca_oauth::{EventId, PublicClient, ResultValue, WcaId, WcaPerson};
+
+fn wca_id(id: &str) -> WcaId {
+	serde_json::from_value(id.into()).unwrap()
+}
+
+/// Serves `body` for every request.
+fn server(body: String) -> (MockServer, PublicClient) {
+	let server = MockServer::start(move |_| Response::ok(body.clone()));
+	let client = PublicClient::with_api_url(server.url());
+	(server, client)
+}
+
+#[test]
+fn persons_are_parsed() {
+	let person: WcaPerson = serde_json::from_str(&common::fixture("person.json")).unwrap();
+	assert_eq!(person.wca_id, wca_id("2015HANS01"));
+	assert_eq!(person.name, "Alice Hansen");
+	assert_eq!(person.gender, "f");
+	assert_eq!(person.country_iso2, "DK");
+	assert_eq!(person.competition_count, 42);
+
+	let avatar = person.avatar.unwrap();
+	assert_eq!(
+		avatar.thumb_url,
+		"https://avatars.worldcubeassociation.org/uploads/user/avatar/2015HANS01/1650000000_thumb.jpg"
+	);
+	assert_eq!(avatar.other["is_default"], false);
+
+	// Sorted by event, with the single before the average. Multi-blind has no average.
+	let records: Vec<_> = person
+		.personal_bests
+		.iter()
+		.map(|pb| (pb.event_id.clone(), pb.t.as_str(), pb.value.clone()))
+		.collect();
+	assert_eq!(
+		records,
+		vec![
+			(EventId::E333, "single", ResultValue::Ok(812)),
+			(EventId::E333, "average", ResultValue::Ok(1034)),
+			(EventId::E333mbf, "single", ResultValue::Ok(930360001)),
+			(EventId::Pyram, "single", ResultValue::Ok(245)),
+			(EventId::Pyram, "average", ResultValue::Ok(498)),
+		]
+	);
+	let average = &person.personal_bests[1];
+	assert_eq!(
+		(
+			average.world_ranking,
+			average.continental_ranking,
+			average.national_ranking
+		),
+		(6031, 2112, 25)
+	);
+}
+
+#[tokio::test]
+async fn gets_a_person() {
+	let (server, client) = server(common::fixture("person.json"));
+	let person = client.get_person("2015HANS01").await.unwrap();
+	assert_eq!(person.wca_id, wca_id("2015HANS01"));
+	assert_eq!(person.personal_bests.len(), 5);
+	assert_eq!(server.requests()[0].target, "/persons/2015HANS01");
+}
+
+#[tokio::test]
+async fn gets_competition_results() {
+	let (server, client) = server(common::fixture("competition_results.json"));
+	let results = client
+		.get_competition_results("TestOpen2024")
+		.await
+		.unwrap();
+	assert_eq!(
+		server.requests()[0].target,
+		"/competitions/TestOpen2024/results"
+	);
+
+	assert_eq!(results.len(), 3);
+	assert_eq!(results[0].wca_id, wca_id("2015HANS01"));
+	assert_eq!(results[0].average, ResultValue::Ok(812));
+	assert_eq!(results[1].average, ResultValue::DNF);
+	assert_eq!(results[1].regional_single_record.as_deref(), Some("NR"));
+	assert_eq!(results[2].event_id, EventId::E333bf);
+	assert_eq!(results[2].average, ResultValue::Skip);
+	assert_eq!(
+		results[2].attempts,
+		vec![ResultValue::DNF, ResultValue::Ok(1845), ResultValue::DNS]
+	);
+}
+
+#[tokio::test]
+async fn gets_a_competition() {
+	let (server, client) = server(common::fixture("competition_api.json"));
+	let competition = client.get_competition("TestOpen2024").await.unwrap();
+	assert_eq!(server.requests()[0].target, "/competitions/TestOpen2024");
+
+	assert_eq!(competition.id(), "TestOpen2024");
+	assert_eq!(competition.competitor_limit, Some(100));
+	assert_eq!(
+		competition.event_ids,
+		vec![
+			EventId::E333,
+			EventId::E222,
+			EventId::E333bf,
+			EventId::Pyram
+		]
+	);
+	assert!(competition.is_announced());
+	assert!(!competition.is_cancelled());
+	assert_eq!(competition.delegates.len(), 2);
+	assert_eq!(competition.organizers.len(), 1);
+}
+
+#[tokio::test]
+async fn unknown_persons_are_wca_errors() {
+	let server = MockServer::start(|_| Response::new(404, r#"{"error":"Not found"}"#));
+	let client = PublicClient::with_api_url(server.url());
+	let error = client.get_person("2099NONE01").await.unwrap_err();
+	assert!(
+		matches!(&error, wca_oauth::WcaApiError::Wca { status, error, .. } if *status == 404 && error == "Not found"),
+		"{error:?}"
+	);
+}
@@ -0,0 +1,36 @@
+mod common;
+
+use serde_json::Value;
+
+#[test]
+fn parses_public_wcif() {
+	let wcif = common::wcif("public.json");
+	let alice = &wcif.get().persons[0];
+	assert_eq!(alice.name, "Alice Example");
+	assert_eq!(alice.birthdate, None);
+	assert_eq!(alice.email, None);
+	let registration = alice.registration.as_ref().unwrap();
+	assert_eq!(registration.status, "accepted");
+	assert_eq!(registration.guests, None);
+	assert_eq!(registration.comments, None);
+	assert_eq!(registration.administrative_notes, None);
+}
+
+#[test]
+fn private_fields_are_not_added() {
+	let wcif = common::wcif("public.json");
+	let expected: Value = serde_json::from_str(&common::fixture("public.json")).unwrap();
+	let json = serde_json::to_value(wcif.get()).unwrap();
+	assert_eq!(json["persons"], expected["persons"]);
+}
+
+#[test]
+fn private_wcif_has_private_fields() {
+	let wcif = common::competition();
+	let carol = &wcif.get().persons[2];
+	assert_eq!(carol.birthdate, Some("2001-01-01".parse().unwrap()));
+	assert_eq!(carol.email.as_deref(), Some("carol@example.com"));
+	let registration = carol.registration.as_ref().unwrap();
+	assert_eq!(registration.guests, Some(0));
+	assert_eq!(registration.comments.as_deref(), Some("late"));
+}
@@ -0,0 +1,105 @@
+#![cfg(feature = "client")]
+
+mod common;
+
+use common::{MockServer, Response};
+use wca_oauth::{OAuth, WcaApiError};
+
+/// A server handing out `access-1` expiring in `expires_in` seconds for the authorization code
+/// and `access-2` for the refresh token. Only `access-2` is accepted for the wcif if `rejects`.
+fn server(expires_in: u64, rejects: bool) -> MockServer {
+	MockServer::start(move |request| match request.path() {
+		"/oauth/token" => match request.form_param("grant_type").unwrap().as_str() {
+			"authorization_code" => common::tokens("access-1", "refresh-1", expires_in),
+			_ => common::tokens("access-2", "refresh-2", 7200),
+		},
+		_ if rejects && request.header("authorization") != Some("Bearer access-2") => {
+			Response::new(401, r#"{"error":"Not logged in"}"#)
+		}
+		_ => Response::ok(common::fixture("competition.json")),
+	})
+}
+
+async fn oauth(server: &MockServer) -> OAuth {
+	OAuth::builder("client".to_string(), "http://localhost:8080/".to_string())
+		.auth_url(server.url())
+		.api_url(server.url())
+		.get_auth("code".to_string())
+		.await
+		.unwrap()
+}
+
+/// The grant type and bearer token of every request, in order.
+fn log(server: &MockServer) -> Vec<String> {
+	server
+		.requests()
+		.iter()
+		.map(|request| match request.form_param("grant_type") {
+			Some(grant_type) => grant_type,
+			None => request.header("authorization").unwrap_or("").to_string(),
+		})
+		.collect()
+}
+
+#[tokio::test]
+async fn valid_tokens_are_not_refreshed() {
+	let server = server(7200, false);
+	let oauth = oauth(&server).await;
+	oauth.get_wcif("TestOpen2024").await.unwrap();
+	oauth.get_wcif("TestOpen2024").await.unwrap();
+	assert_eq!(
+		log(&server),
+		["authorization_code", "Bearer access-1", "Bearer access-1"]
+	);
+}
+
+#[tokio::test]
+async fn tokens_are_refreshed_before_they_expire() {
+	let server = server(30, false);
+	let oauth = oauth(&server).await;
+	oauth.get_wcif("TestOpen2024").await.unwrap();
+	oauth.get_wcif("TestOpen2024").await.unwrap();
+	assert_eq!(
+		log(&server),
+		[
+			"authorization_code",
+			"refresh_token",
+			"Bearer access-2",
+			"Bearer access-2"
+		]
+	);
+	let refresh = &server.requests()[1];
+	assert_eq!(refresh.form_param("refresh_token").unwrap(), "refresh-1");
+	assert_eq!(refresh.form_param("client_id").unwrap(), "client");
+}
+
+#[tokio::test]
+async fn rejected_tokens_are_refreshed_once() {
+	let server = server(7200, true);
+	let oauth = oauth(&server).await;
+	oauth.get_wcif("TestOpen2024").await.unwrap();
+	oauth.get_wcif("TestOpen2024").await.unwrap();
+	assert_eq!(
+		log(&server),
+		[
+			"authorization_code",
+			"Bearer access-1",
+			"refresh_token",
+			"Bearer access-2",
+			"Bearer access-2"
+		]
+	);
+}
+
+#[tokio::test]
+async fn implicit_tokens_cannot_be_refreshed() {
+	let server = server(7200, true);
+	let oauth = OAuth::builder("client".to_string(), "http://localhost:8080/".to_string())
+		.api_url(server.url())
+		.implicit("access-1".to_string());
+	match oauth.get_wcif("TestOpen2024").await {
+		Err(WcaApiError::ReauthenticationRequired) => (),
+		other => panic!("expected to require authentication, got {other:?}"),
+	}
+	assert_eq!(log(&server), ["Bearer access-1"]);
+}
@@ -0,0 +1,145 @@
+#![cfg(feature = "client")]
+
+mod common;
+
+use std::time::Duration;
+
+use common::{MockServer, Response};
+use wca_oauth::{OAuth, PublicClient, RetryPolicy, WcaApiError};
+
+fn millis(millis: u64) -> Duration {
+	Duration::from_millis(millis)
+}
+
+/// Answers with `status` until `failures` requests failed, then with the wcif.
+fn failing(failures: usize, status: u16) -> impl FnMut(&common::Request) -> Response {
+	let mut count = 0;
+	move |request| {
+		count += 1;
+		if request.method != "GET" || count > failures {
+			Response::ok(common::fixture("competition.json"))
+		} else {
+			Response::new(status, "busy")
+		}
+	}
+}
+
+#[test]
+fn backoff_doubles_up_to_the_maximum() {
+	let policy = RetryPolicy::default()
+		.backoff(millis(100), millis(500))
+		.jitter(false);
+	let backoffs: Vec<_> = (0..5).map(|retry| policy.backoff_for(retry)).collect();
+	assert_eq!(
+		backoffs,
+		[
+			millis(100),
+			millis(200),
+			millis(400),
+			millis(500),
+			millis(500)
+		]
+	);
+	assert_eq!(policy.backoff_for(u32::MAX), millis(500));
+}
+
+#[test]
+fn jitter_waits_between_half_and_all_of_the_backoff() {
+	let policy = RetryPolicy::default().backoff(millis(100), millis(500));
+	for retry in 0..5 {
+		let backoff = policy.clone().jitter(false).backoff_for(retry);
+		for _ in 0..20 {
+			let jittered = policy.backoff_for(retry);
+			assert!(jittered >= backoff / 2 && jittered <= backoff);
+		}
+	}
+}
+
+#[tokio::test]
+async fn retries_get_requests() {
+	let server = MockServer::start(failing(2, 503));
+	let client = PublicClient::with_api_url(server.url())
+		.retry_policy(RetryPolicy::default().backoff(millis(1), millis(1)));
+	client.get_wcif("Test2024").await.unwrap();
+	assert_eq!(server.requests().len(), 3);
+
+	let server = MockServer::start(failing(3, 503));
+	let client = PublicClient::with_api_url(server.url())
+		.retry_policy(RetryPolicy::default().backoff(millis(1), millis(1)));
+	match client.get_wcif("Test2024").await {
+		Err(WcaApiError::Http { status, body }) => {
+			assert_eq!(status.as_u16(), 503);
+			assert_eq!(body, "busy");
+		}
+		other => panic!("expected the last error, got {other:?}"),
+	}
+	assert_eq!(server.requests().len(), 3);
+}
+
+#[tokio::test]
+async fn client_errors_are_not_retried() {
+	let server = MockServer::start(failing(1, 404));
+	let client = PublicClient::with_api_url(server.url())
+		.retry_policy(RetryPolicy::default().backoff(millis(1), millis(1)));
+	assert!(client.get_wcif("Test2024").await.is_err());
+	assert_eq!(server.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn retry_after_is_capped_by_the_maximum_backoff() {
+	let mut count = 0;
+	let server = MockServer::start(move |_| {
+		count += 1;
+		match count {
+			1 => Response::new(429, "slow down").header("Retry-After", "3600"),
+			_ => Response::ok(common::fixture("competition.json")),
+		}
+	});
+	let client = PublicClient::with_api_url(server.url())
+		.retry_policy(RetryPolicy::default().backoff(millis(1), millis(50)));
+	tokio::time::timeout(Duration::from_secs(10), client.get_wcif("Test2024"))
+		.await
+		.expect("waited for the hour asked for")
+		.unwrap();
+	assert_eq!(server.requests().len(), 2);
+}
+
+/// Patches a change against a server failing the first patch. Returns whether the patch
+/// succeeded and how many patch requests were sent.
+async fn patch_requests(retry_policy: RetryPolicy) -> (bool, usize) {
+	let mut patches = 0;
+	let server = MockServer::start(move |request| {
+		if request.method == "PATCH" {
+			patches += 1;
+			if patches == 1 {
+				return Response::new(503, "busy");
+			}
+		}
+		Response::ok(common::fixture("competition.json"))
+	});
+	let oauth = OAuth::builder(
+		"client".to_string(),
+		"urn:ietf:wg:oauth:2.0:oob".to_string(),
+	)
+	.api_url(server.url())
+	.retry_policy(retry_policy.backoff(millis(1), millis(1)))
+	.implicit("token".to_string());
+	let mut wcif = common::competition();
+	wcif.get_mut().schedule.venues[0].name = "Patched Hall".to_string();
+	let patched = wcif.patch(&oauth).await.is_ok();
+	let patches = server
+		.requests()
+		.iter()
+		.filter(|request| request.method == "PATCH")
+		.count();
+	(patched, patches)
+}
+
+#[tokio::test]
+async fn patch_requests_are_only_retried_when_allowed() {
+	assert_eq!(patch_requests(RetryPolicy::default()).await, (false, 1));
+	assert_eq!(
+		patch_requests(RetryPolicy::default().retry_patch(true)).await,
+		(true, 2)
+	);
+}
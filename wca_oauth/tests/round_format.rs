@@ -0,0 +1,66 @@
+mod common;
+
+use serde_json::json;
+use wca_oauth::{Cutoff, OtherFields, RankingType, ResultValue, RoundFormat};
+
+#[test]
+fn parses_round_formats() {
+	let formats: Vec<RoundFormat> =
+		serde_json::from_value(json!(["1", "2", "3", "5", "a", "m", "h", "x"])).unwrap();
+	assert_eq!(
+		formats,
+		[
+			RoundFormat::BestOf1,
+			RoundFormat::BestOf2,
+			RoundFormat::BestOf3,
+			RoundFormat::BestOf5,
+			RoundFormat::Average,
+			RoundFormat::Mean,
+			RoundFormat::HeadToHead,
+			RoundFormat::Other('x'),
+		]
+	);
+	assert_eq!(
+		serde_json::to_value(&formats).unwrap(),
+		json!(["1", "2", "3", "5", "a", "m", "h", "x"])
+	);
+}
+
+#[test]
+fn round_format_metadata() {
+	assert_eq!(RoundFormat::Average.attempts(), Some(5));
+	assert_eq!(RoundFormat::Mean.attempts(), Some(3));
+	assert_eq!(RoundFormat::BestOf2.attempts(), Some(2));
+	assert_eq!(RoundFormat::HeadToHead.attempts(), None);
+
+	assert_eq!(RoundFormat::Average.ranking(), RankingType::Average);
+	assert_eq!(RoundFormat::Mean.ranking().scope(), "average");
+	assert_eq!(RoundFormat::BestOf3.ranking(), RankingType::Single);
+
+	assert_eq!(RoundFormat::Mean.to_string(), "Mean of 3");
+	assert_eq!(RoundFormat::Average.id(), 'a');
+}
+
+#[test]
+fn cutoffs() {
+	let cutoff = |number_of_attempts| Cutoff {
+		number_of_attempts,
+		result_value: ResultValue::Ok(6000),
+		other: OtherFields::new(),
+	};
+	assert!(RoundFormat::Average.allows_cutoff(&cutoff(2)));
+	assert!(!RoundFormat::Average.allows_cutoff(&cutoff(3)));
+	assert!(RoundFormat::BestOf2.allows_cutoff(&cutoff(1)));
+	assert!(!RoundFormat::BestOf1.allows_cutoff(&cutoff(1)));
+}
+
+#[test]
+fn rounds_have_formats() {
+	let wcif = common::competition();
+	let round = wcif.round_iter().next().unwrap();
+	assert_eq!(round.format, RoundFormat::Average);
+	assert!(round
+		.cutoff
+		.as_ref()
+		.is_none_or(|cutoff| round.format.allows_cutoff(cutoff)));
+}
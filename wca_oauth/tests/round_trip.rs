@@ -0,0 +1,72 @@
+mod common;
+
+use serde_json::Value;
+use wca_oauth::Wcif;
+
+/// Parses and serializes a fixture, which has to give back exactly the same json, without any
+/// field, not even a null one, added or dropped.
+fn assert_round_trip(fixture: &str) {
+	let json = common::fixture(fixture);
+	let wcif: Wcif = serde_json::from_str(&json).unwrap();
+	let expected: Value = serde_json::from_str(&json).unwrap();
+	assert_eq!(serde_json::to_value(&wcif).unwrap(), expected);
+}
+
+#[test]
+fn round_trip_competition() {
+	assert_round_trip("competition.json");
+}
+
+#[test]
+fn round_trip_third_party_fields() {
+	assert_round_trip("third_party.json");
+}
+
+#[test]
+fn unknown_fields_survive_changes() {
+	let json = common::fixture("third_party.json");
+	let mut wcif: Wcif = serde_json::from_str(&json).unwrap();
+	wcif.persons[0].assignments.clear();
+	let json = serde_json::to_value(&wcif).unwrap();
+	assert_eq!(json["persons"][0]["registration"]["paymentStatus"], "paid");
+	assert_eq!(json["events"][0]["rounds"][0]["scrambleSets"][0]["id"], 1);
+	assert_eq!(json["events"][0]["competitorLimit"], 40);
+}
+
+#[test]
+fn activity_codes_of_other_tools_survive() {
+	let mut json: Value = serde_json::from_str(&common::fixture("third_party.json")).unwrap();
+	let activity = &mut json["schedule"]["venues"][0]["rooms"][0]["activities"][3];
+	activity["activityCode"] = Value::from("333-r02");
+	let wcif: Wcif = serde_json::from_value(json.clone()).unwrap();
+	assert_eq!(serde_json::to_value(&wcif).unwrap(), json);
+}
+
+#[test]
+fn round_trip_wca_export() {
+	// Rounds without scramble sets, activities without scramble set ids and events without
+	// competitor limits, like the WCA sends them.
+	assert_round_trip("wca_export.json");
+}
+
+#[test]
+fn patches_only_contain_fields_of_the_input() {
+	let mut wcif = common::wcif("wca_export.json");
+	wcif.get_mut().events[0].rounds[0].scramble_set_count = 3;
+	wcif.get_mut().schedule.venues[0].name = "Main Hall".to_string();
+	let changes = wcif.changes().unwrap();
+	let expected: Value = serde_json::from_str(&common::fixture("wca_export.json")).unwrap();
+	let round = &changes["events"][0]["rounds"][0];
+	assert_eq!(round["scrambleSetCount"], 3);
+	for key in round.as_object().unwrap().keys() {
+		assert!(
+			expected["events"][0]["rounds"][0].get(key).is_some(),
+			"{key}"
+		);
+	}
+	assert!(changes["events"][0].get("competitorLimit").is_none());
+	assert_eq!(
+		changes["schedule"]["venues"][0]["rooms"],
+		expected["schedule"]["venues"][0]["rooms"]
+	);
+}
@@ -0,0 +1,101 @@
+mod common;
+
+use wca_oauth::{EventId, LinkScrambleSetsError, Scramble, ScrambleSetMismatch, WcifContainer};
+
+fn scramble_set_ids(wcif: &WcifContainer, round_id: &str) -> Vec<Option<u64>> {
+	wcif.schedule_activity_iter()
+		.flat_map(|activity| &activity.child_activities)
+		.filter(|activity| activity.activity_code.round_id().as_deref() == Some(round_id))
+		.map(|activity| activity.scramble_set_id)
+		.collect()
+}
+
+#[test]
+fn scramble_sets_are_parsed() {
+	let wcif = common::competition();
+	let round = wcif
+		.round_iter()
+		.find(|round| round.id == "333-r1")
+		.unwrap();
+	assert_eq!(round.scramble_sets.len(), 2);
+	assert_eq!(
+		round.scramble_sets[0].scramble_for_attempt(2),
+		Some(&Scramble::from("F2 L2 B"))
+	);
+	assert_eq!(round.scramble_sets[0].scramble_for_attempt(0), None);
+
+	let group = wcif
+		.schedule_activity_iter()
+		.flat_map(|activity| &activity.child_activities)
+		.find(|activity| activity.activity_code.to_string() == "333-r1-g2")
+		.unwrap();
+	assert_eq!(wcif.scramble_set_of(group).map(|set| set.id), Some(2));
+}
+
+#[test]
+fn links_groups_to_scramble_sets() {
+	let mut wcif = common::competition();
+	assert_eq!(wcif.round_groups("333-r1"), vec![(1, 1), (1, 2)]);
+	wcif.patch_rounds(|round| round.scramble_sets.reverse());
+	wcif.link_scramble_sets("333-r1").unwrap();
+	assert_eq!(scramble_set_ids(&wcif, "333-r1"), vec![Some(2), Some(1)]);
+
+	wcif.add_groups_to_event(&EventId::E333, 2, 2, 1).unwrap();
+	assert_eq!(
+		wcif.link_scramble_sets("333-r2"),
+		Err(LinkScrambleSetsError::Mismatch(ScrambleSetMismatch {
+			round_id: "333-r2".to_string(),
+			groups: 2,
+			scramble_sets: 0,
+		}))
+	);
+	assert_eq!(scramble_set_ids(&wcif, "333-r2"), vec![None, None]);
+}
+
+#[test]
+fn linking_an_unknown_round_fails() {
+	let mut wcif = common::competition();
+	let error = wcif.link_scramble_sets("999-r9").unwrap_err();
+	assert_eq!(
+		error,
+		LinkScrambleSetsError::UnknownRound("999-r9".to_string())
+	);
+	assert_eq!(error.to_string(), "there is no round 999-r9");
+	assert_eq!(scramble_set_ids(&wcif, "333-r1"), vec![Some(1), Some(2)]);
+}
+
+#[test]
+fn checks_scramble_set_counts() {
+	let mut wcif = common::competition();
+	assert!(wcif.check_scramble_set_counts().is_empty());
+
+	wcif.add_groups_to_event(&EventId::E333, 2, 3, 1).unwrap();
+	let mismatches = wcif.check_scramble_set_counts();
+	assert_eq!(
+		mismatches,
+		vec![ScrambleSetMismatch {
+			round_id: "333-r2".to_string(),
+			groups: 3,
+			scramble_sets: 1,
+		}]
+	);
+	assert_eq!(
+		mismatches[0].to_string(),
+		"333-r2 has 3 groups but 1 scramble sets"
+	);
+}
+
+#[test]
+fn multi_blind_and_fewest_moves_scrambles() {
+	let multi: Scramble = serde_json::from_str("\"R U\\nF2 D\\n\\nL' B\"").unwrap();
+	assert_eq!(multi.cube_count(), 3);
+	assert_eq!(multi.cubes().last(), Some("L' B"));
+	assert_eq!(
+		serde_json::to_string(&multi).unwrap(),
+		"\"R U\\nF2 D\\n\\nL' B\""
+	);
+
+	assert!(Scramble::from("R' U' F D2 L R' U' F").is_valid_fmc());
+	assert!(!Scramble::from("R' U' F D2 L").is_valid_fmc());
+	assert_eq!(Scramble::from("R' U' F D2 L R' U' F").moves().count(), 8);
+}
@@ -0,0 +1,121 @@
+#![cfg(feature = "client")]
+
+mod common;
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use common::{MockServer, Response};
+use wca_oauth::{OAuth, OAuthBuilder, RetryPolicy, SnapshotStore};
+
+/// A server handing out `access-1` expiring in `expires_in` seconds for the authorization code
+/// and `access-2` for the refresh token, and serving the wcif to anyone.
+fn server(expires_in: u64) -> MockServer {
+	MockServer::start(move |request| match request.path() {
+		"/oauth/token" => match request.form_param("grant_type").unwrap().as_str() {
+			"authorization_code" => common::tokens("access-1", "refresh-1", expires_in),
+			_ => common::tokens("access-2", "refresh-2", 7200),
+		},
+		_ => Response::ok(common::fixture("competition.json")),
+	})
+}
+
+fn builder(server: &MockServer) -> OAuthBuilder {
+	OAuth::builder("client".to_string(), "http://localhost:8080/".to_string())
+		.client_secret("secret".to_string())
+		.auth_url(server.url())
+		.api_url(server.url())
+}
+
+fn grants(server: &MockServer) -> Vec<String> {
+	server
+		.requests()
+		.iter()
+		.filter(|request| request.path() == "/oauth/token")
+		.map(|request| request.form_param("grant_type").unwrap())
+		.collect()
+}
+
+fn secs(time: Option<SystemTime>) -> u64 {
+	time.unwrap().duration_since(UNIX_EPOCH).unwrap().as_secs()
+}
+
+fn bearer(server: &MockServer) -> Option<String> {
+	let requests = server.requests();
+	let request = requests.iter().rev().find(|r| r.method == "GET")?;
+	request.header("authorization").map(str::to_string)
+}
+
+#[tokio::test]
+async fn sessions_are_restored_with_the_settings_of_the_builder() {
+	let server = server(7200);
+	let path = common::temp_dir("sessions_are_restored").join("session.json");
+	let oauth = builder(&server).get_auth("code".to_string()).await.unwrap();
+	oauth.save(&path).await.unwrap();
+
+	let retry_policy = RetryPolicy::none().retry_patch(true);
+	let store = SnapshotStore::new(common::temp_dir("sessions_are_restored_snapshots"));
+	let loaded = OAuth::builder(String::new(), String::new())
+		.retry_policy(retry_policy.clone())
+		.snapshot_store(store.clone())
+		.load_private(&path)
+		.await
+		.unwrap();
+	assert_eq!(loaded.retry_policy(), &retry_policy);
+	assert_eq!(loaded.snapshot_store().unwrap().dir(), store.dir());
+	assert_eq!(loaded.api_url(), server.url());
+	assert_eq!(
+		secs(loaded.expires_at().await),
+		secs(oauth.expires_at().await)
+	);
+
+	// The token is still valid, so it is used as it is.
+	loaded.get_wcif("TestOpen2024").await.unwrap();
+	assert_eq!(grants(&server), ["authorization_code"]);
+	assert_eq!(bearer(&server).as_deref(), Some("Bearer access-1"));
+
+	let defaults = OAuth::load(&path).await.unwrap();
+	assert_eq!(defaults.retry_policy(), &RetryPolicy::default());
+	assert!(defaults.snapshot_store().is_none());
+}
+
+#[tokio::test]
+async fn stale_tokens_are_refreshed_on_load() {
+	// Expires within the margin in which tokens are refreshed.
+	let server = server(30);
+	let path = common::temp_dir("stale_tokens_are_refreshed").join("session.json");
+	let oauth = builder(&server).get_auth("code".to_string()).await.unwrap();
+	oauth.save(&path).await.unwrap();
+
+	let loaded = OAuth::load(&path).await.unwrap();
+	assert_eq!(grants(&server), ["authorization_code", "refresh_token"]);
+	let refresh = server.requests().pop().unwrap();
+	assert!(refresh
+		.form()
+		.contains(&("refresh_token".to_string(), "refresh-1".to_string())));
+	assert!(refresh
+		.form()
+		.contains(&("client_secret".to_string(), "secret".to_string())));
+
+	loaded.get_wcif("TestOpen2024").await.unwrap();
+	assert_eq!(bearer(&server).as_deref(), Some("Bearer access-2"));
+	assert_eq!(grants(&server).len(), 2);
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn private_sessions_must_not_be_readable_by_others() {
+	use std::os::unix::fs::PermissionsExt;
+
+	let server = server(7200);
+	let path = common::temp_dir("private_sessions").join("session.json");
+	let oauth = builder(&server).get_auth("code".to_string()).await.unwrap();
+	oauth.save(&path).await.unwrap();
+	assert_eq!(
+		std::fs::metadata(&path).unwrap().permissions().mode() & 0o777,
+		0o600
+	);
+
+	std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+	assert!(OAuth::load_private(&path).await.is_err());
+	assert!(OAuth::load(&path).await.is_ok());
+}
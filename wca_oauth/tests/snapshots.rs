@@ -0,0 +1,85 @@
+#![cfg(feature = "client")]
+
+mod common;
+
+use common::{MockServer, Response};
+use wca_oauth::{DateTime, OAuth, SnapshotStore, Wcif, WcifContainer};
+
+fn shifted() -> Wcif {
+	let mut wcif = common::competition_wcif();
+	let activity = &mut wcif.schedule.venues[0].rooms[0].activities[1];
+	activity.start_time = "2024-05-04T08:00:00".parse::<DateTime>().unwrap();
+	activity.end_time = "2024-05-04T09:00:00".parse::<DateTime>().unwrap();
+	wcif
+}
+
+#[test]
+fn snapshots_taken_at_once_are_all_kept() {
+	let store = SnapshotStore::new(common::temp_dir("snapshots_taken_at_once"));
+	let wcif = common::competition_wcif();
+	let saved: Vec<_> = ["first", "second", "third"]
+		.into_iter()
+		.map(|reason| store.save(&wcif, reason).unwrap())
+		.collect();
+
+	let listed = store.list("TestOpen2024").unwrap();
+	assert_eq!(listed, saved);
+	let reasons: Vec<_> = listed.iter().map(|s| s.reason.as_str()).collect();
+	assert_eq!(reasons, ["first", "second", "third"]);
+	assert_eq!(store.latest("TestOpen2024").unwrap().as_ref(), saved.last());
+	assert_eq!(store.load(&listed[1]).unwrap(), wcif);
+
+	assert!(store.list("OtherOpen2024").unwrap().is_empty());
+	assert_eq!(store.latest("OtherOpen2024").unwrap(), None);
+}
+
+#[test]
+fn snapshots_are_diffed() {
+	let store = SnapshotStore::new(common::temp_dir("snapshots_are_diffed"));
+	let before = store.save(&common::competition_wcif(), "patch").unwrap();
+	let after = store.save(&shifted(), "patch").unwrap();
+
+	assert!(store.diff(&before, &before).unwrap().is_empty());
+	let diff = store.diff(&before, &after).unwrap();
+	assert_eq!(diff.time_shifts.len(), 1);
+	assert_eq!(diff.time_shifts[0].id, 2);
+	assert!(diff.persons_added.is_empty() && diff.activities_added.is_empty());
+}
+
+#[tokio::test]
+async fn restoring_patches_the_snapshot() {
+	let store = SnapshotStore::new(common::temp_dir("restoring_patches_the_snapshot"));
+	let snapshot = store.save(&common::competition_wcif(), "patch").unwrap();
+
+	let remote = serde_json::to_string(&shifted()).unwrap();
+	let server = MockServer::start(move |_| Response::ok(remote.clone()));
+	let oauth = OAuth::builder(
+		"client".to_string(),
+		"urn:ietf:wg:oauth:2.0:oob".to_string(),
+	)
+	.api_url(server.url())
+	.snapshot_store(store.clone())
+	.implicit("token".to_string());
+
+	let mut wcif = WcifContainer::new(shifted());
+	wcif.restore(&store, &snapshot, &oauth).await.unwrap();
+	assert_eq!(wcif.get(), &common::competition_wcif());
+	assert_eq!(wcif.changes(), None);
+
+	let requests = server.requests();
+	let patch = requests.iter().find(|r| r.method == "PATCH").unwrap();
+	assert_eq!(patch.path(), "/competitions/TestOpen2024/wcif");
+	let body: serde_json::Value = serde_json::from_str(&patch.body).unwrap();
+	let snapshot_json = serde_json::to_value(store.load(&snapshot).unwrap()).unwrap();
+	assert_eq!(body["schedule"], snapshot_json["schedule"]);
+	assert!(body.get("persons").is_none());
+
+	// The remote wcif was saved before it was overwritten.
+	let snapshots = store.list("TestOpen2024").unwrap();
+	assert_eq!(snapshots.len(), 2);
+	assert_eq!(
+		snapshots[1].reason,
+		format!("restore snapshot from {}", snapshot.taken_at)
+	);
+	assert_eq!(store.load(&snapshots[1]).unwrap(), shifted());
+}
@@ -5,7 +5,7 @@ mod localhost;
 mod pdf;
 pub mod wcif;
 
-pub use localhost::responses::generate_pdf;
+pub use localhost::responses::{generate_pdf, generate_pdf_dry_run, GroupsError};
 pub use pdf::Stages;
 
 #[allow(deprecated)]
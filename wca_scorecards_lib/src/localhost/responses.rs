@@ -1,10 +1,39 @@
+use std::fmt::{Display, Formatter};
+
 use scorecard_to_pdf::Return;
-use wca_oauth::{Assignment, AssignmentCode, WcifOAuth};
+use wca_oauth::{
+	Assignment, AssignmentCode, ChangeSummary, EventId, OtherFields, WcifContainer, WcifOAuth,
+};
 
 use crate::{ScorecardOrdering, Stages};
 
+/// Why groups could not be added to the wcif.
+#[derive(Debug)]
+pub enum GroupsError {
+	/// The round has no activity in the schedule to add the groups to.
+	RoundNotScheduled { event: EventId, round: usize },
+	/// The changes could not be summarized, because the wcif has no original to compare with or
+	/// it could not be read.
+	Summary(String),
+}
+
+impl Display for GroupsError {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		match self {
+			GroupsError::RoundNotScheduled { event, round } => write!(
+				f,
+				"round {round} of {} is not in the schedule",
+				event.name()
+			),
+			GroupsError::Summary(e) => write!(f, "unable to summarize the changes: {e}"),
+		}
+	}
+}
+
+impl std::error::Error for GroupsError {}
+
 pub async fn generate_pdf(
-	eventid: &str,
+	eventid: &EventId,
 	round: usize,
 	groups: Vec<Vec<usize>>,
 	wcif: bool,
@@ -12,94 +41,26 @@ pub async fn generate_pdf(
 	stages: &Stages,
 	compare: ScorecardOrdering,
 ) -> Return {
-	fn assign_stages(groups: Vec<Vec<usize>>, stages: &Stages) -> Vec<Vec<(usize, usize)>> {
-		groups
-			.into_iter()
-			.map(|group| {
-				//let no_of_stages = (group.len() + stages.capacity as usize - 1) / stages.capacity as usize;
-				(0..stages.no)
-					.cycle()
-					.zip(group)
-					.enumerate()
-					.map(|(idx, (i, g))| {
-						let station =
-							stages.capacity as usize * i as usize + idx / stages.no as usize + 1;
-						(g, station)
-					})
-					.collect()
-			})
-			.collect()
-	}
-
 	let groups_with_stations = assign_stages(groups.clone(), stages);
 
 	if wcif {
-		match wcif_oauth.add_groups_to_event(
+		match add_groups(
 			eventid,
 			round,
-			groups.len(),
-			if stages.seperate_stages {
-				stages.no as usize
-			} else {
-				1
-			},
+			&groups,
+			&groups_with_stations,
+			wcif_oauth,
+			stages,
 		) {
-			Ok(activities) => {
-				let activity_ids: Vec<_> = activities.into_iter().map(|act| act.id).collect();
-				let groups_with_stations = if stages.seperate_stages {
-					groups_with_stations
-						.iter()
-						.flat_map(|group| {
-							let mut interior_groups = vec![vec![]; stages.no as usize];
-							for (id, station) in group {
-								interior_groups[(station - 1) / stages.capacity as usize]
-									.push((*id, *station));
-							}
-							interior_groups
-						})
-						.collect()
-				} else {
-					groups_with_stations.clone()
-				};
-				for (group, (&activity_id, &next_activity_id)) in
-					groups_with_stations.iter().zip(activity_ids.iter().zip(
-						activity_ids.iter().cycle().skip(if stages.seperate_stages {
-							stages.no as usize
-						} else {
-							1
-						}),
-					)) {
-					for (id, station) in group.into_iter() {
-						//This runs in O(nm) time which is horrible, when it could run in O(n) time but n and m are both small so i will let it be for now :)
-						wcif_oauth.patch_persons(|person| {
-							if person.registrant_id == Some(*id) {
-								// Push competing assignet to current group
-								person.assignments.push(Assignment {
-									activity_id,
-									assignment_code: AssignmentCode::Competitor,
-									station_number: Some(*station),
-								});
-								if activity_id != next_activity_id {
-									// Push judge assignment to next group
-									person.assignments.push(Assignment {
-										activity_id: next_activity_id,
-										assignment_code: AssignmentCode::Judge,
-										station_number: None,
-									});
-								}
-							}
-						});
-					}
+			Ok(()) => {
+				let summary = change_summary(wcif_oauth);
+				match (wcif_oauth.patch().await, summary) {
+					(Ok(_), Ok(summary)) => println!("Patched to wcif:\n{}", summary),
+					(Ok(_), Err(_)) => println!("Patched to wcif."),
+					(Err(e), _) => println!("Unable to patch the wcif: {}", e),
 				}
-				let response = wcif_oauth.patch().await;
-				println!(
-					"Patched to wcif. Received the following response: \n{}",
-					response
-				);
 			}
-			Err(()) => println!(
-				"Unable to patch likely because the given event already has groups in the wcif."
-			),
+			Err(e) => println!("Unable to add the groups to the wcif: {}", e),
 		}
 	}
 
@@ -112,3 +73,143 @@ pub async fn generate_pdf(
 		compare,
 	)
 }
+
+/// Same as `generate_pdf` with `wcif` set, except that the groups are only added to the local
+/// wcif. Returns a summary of the changes, which can be patched with `WcifOAuth::patch` once they
+/// are confirmed.
+pub fn generate_pdf_dry_run(
+	eventid: &EventId,
+	round: usize,
+	groups: Vec<Vec<usize>>,
+	wcif_oauth: &mut WcifOAuth,
+	stages: &Stages,
+	compare: ScorecardOrdering,
+) -> Result<(Return, ChangeSummary), GroupsError> {
+	let groups_with_stations = assign_stages(groups.clone(), stages);
+	add_groups(
+		eventid,
+		round,
+		&groups,
+		&groups_with_stations,
+		wcif_oauth,
+		stages,
+	)?;
+	let summary = change_summary(wcif_oauth)?;
+
+	let pdf = crate::pdf::run_from_wcif(
+		wcif_oauth,
+		eventid,
+		round,
+		groups_with_stations,
+		stages,
+		compare,
+	);
+	Ok((pdf, summary))
+}
+
+fn change_summary(wcif: &WcifContainer) -> Result<ChangeSummary, GroupsError> {
+	wcif.change_summary()
+		.map_err(|e| GroupsError::Summary(e.to_string()))?
+		.ok_or_else(|| GroupsError::Summary("the wcif has no original".to_string()))
+}
+
+fn assign_stages(groups: Vec<Vec<usize>>, stages: &Stages) -> Vec<Vec<(usize, usize)>> {
+	groups
+		.into_iter()
+		.map(|group| {
+			//let no_of_stages = (group.len() + stages.capacity as usize - 1) / stages.capacity as usize;
+			(0..stages.no)
+				.cycle()
+				.zip(group)
+				.enumerate()
+				.map(|(idx, (i, g))| {
+					let station =
+						stages.capacity as usize * i as usize + idx / stages.no as usize + 1;
+					(g, station)
+				})
+				.collect()
+		})
+		.collect()
+}
+
+/// Adds the groups as child activities of the round, assigns the competitors and judges to them
+/// and sets the scramble set count of the round to the number of groups.
+fn add_groups(
+	eventid: &EventId,
+	round: usize,
+	groups: &[Vec<usize>],
+	groups_with_stations: &[Vec<(usize, usize)>],
+	wcif: &mut WcifContainer,
+	stages: &Stages,
+) -> Result<(), GroupsError> {
+	match wcif.add_groups_to_event(
+		eventid,
+		round,
+		groups.len(),
+		if stages.seperate_stages {
+			stages.no as usize
+		} else {
+			1
+		},
+	) {
+		Ok(activities) => {
+			let activity_ids: Vec<_> = activities.into_iter().map(|act| act.id).collect();
+			let groups_with_stations = if stages.seperate_stages {
+				groups_with_stations
+					.iter()
+					.flat_map(|group| {
+						let mut interior_groups = vec![vec![]; stages.no as usize];
+						for (id, station) in group {
+							interior_groups[(station - 1) / stages.capacity as usize]
+								.push((*id, *station));
+						}
+						interior_groups
+					})
+					.collect()
+			} else {
+				groups_with_stations.to_vec()
+			};
+			for (group, (&activity_id, &next_activity_id)) in
+				groups_with_stations.iter().zip(activity_ids.iter().zip(
+					activity_ids.iter().cycle().skip(if stages.seperate_stages {
+						stages.no as usize
+					} else {
+						1
+					}),
+				)) {
+				for (id, station) in group.into_iter() {
+					//This runs in O(nm) time which is horrible, when it could run in O(n) time but n and m are both small so i will let it be for now :)
+					wcif.patch_persons(|person| {
+						if person.registrant_id == Some(*id) {
+							// Push competing assignet to current group
+							person.assignments.push(Assignment {
+								activity_id,
+								assignment_code: AssignmentCode::Competitor,
+								station_number: Some(*station),
+								other: OtherFields::new(),
+							});
+							if activity_id != next_activity_id {
+								// Push judge assignment to next group
+								person.assignments.push(Assignment {
+									activity_id: next_activity_id,
+									assignment_code: AssignmentCode::Judge,
+									station_number: None,
+									other: OtherFields::new(),
+								});
+							}
+						}
+					});
+				}
+			}
+			// One scramble set per group, which is part of the change like the groups are.
+			if let Some(round_json) = crate::wcif::get_round_json(wcif, eventid, round) {
+				round_json.scramble_set_count = groups.len();
+			}
+			Ok(())
+		}
+		Err(()) => Err(GroupsError::RoundNotScheduled {
+			event: eventid.clone(),
+			round,
+		}),
+	}
+}
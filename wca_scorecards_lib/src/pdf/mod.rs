@@ -4,7 +4,7 @@ use scorecard_to_pdf::Return;
 use scorecard_to_pdf::{scorecards_to_pdf, Language, Scorecard, TimeLimit};
 use std::fs::File;
 use std::{collections::HashMap, io::Write};
-use wca_oauth::{ResultCondition, Wcif, WcifContainer};
+use wca_oauth::{EventId, ResultCondition, Wcif, WcifContainer};
 
 #[derive(Clone)]
 pub struct Stages {
@@ -50,7 +50,13 @@ pub(crate) fn run(
 	//Header describing csv file formatting. First two are fixed and therfore skipped.
 	//Unwrap cannot fail because the first element of lines always exists, although skip can lead
 	//to panic later when used.
-	let header = groups_csv.next().unwrap().split(",").skip(2);
+	let header: Vec<EventId> = groups_csv
+		.next()
+		.unwrap()
+		.split(",")
+		.skip(2)
+		.map(EventId::from)
+		.collect();
 	let no_rounds = groups_csv.next().unwrap().split(",").skip(2).map(|x| x.parse::<usize>().expect("number of rounds must be a positive interger"));
 	let mut map = HashMap::new();
 	let mut k = groups_csv
@@ -79,8 +85,8 @@ pub(crate) fn run(
 			};
 			//Insert the competitor into the id to name map.
 			map.insert(id, name.to_string());
-			//Zipping with header (clone) to know the order of events.
-			iter.zip(header.clone()).zip(no_rounds.clone()).filter_map(move |((asign, event), no)| {
+			//Zipping with header to know the order of events.
+			iter.zip(header.iter()).zip(no_rounds.clone()).filter_map(move |((asign, event), no)| {
 				//Test whether competitor is assigned.
 				if asign == "" {
 					return None;
@@ -111,6 +117,7 @@ pub(crate) fn run(
 			id: Some(id),
 			group,
 			round: r,
+			format: None,
 			station,
 			event,
 			stage: station.map(|x| (x as u32 - 1) / stages.capacity),
@@ -136,26 +143,27 @@ pub(crate) fn run(
 				let mut iter = x.split(";");
 				let v = match iter.next() {
 					None => {
-						limits.insert(event, TimeLimit::None);
+						limits.insert(event.into(), TimeLimit::None);
 						return;
 					}
 					Some(v) => v,
 				};
+				let event = EventId::from(event);
 				match v {
-					"T" => limits.insert(event, TimeLimit::Single(usize_from_iter(&mut iter))),
-					"C" => limits.insert(event, TimeLimit::Cumulative(usize_from_iter(&mut iter))),
+					"T" => limits.insert(event.clone(), TimeLimit::Single(usize_from_iter(&mut iter))),
+					"C" => limits.insert(event.clone(), TimeLimit::Cumulative(usize_from_iter(&mut iter))),
 					"K" => limits.insert(
-						event,
+						event.clone(),
 						TimeLimit::Cutoff(usize_from_iter(&mut iter), usize_from_iter(&mut iter)),
 					),
 					"S" => limits.insert(
-						event,
+						event.clone(),
 						TimeLimit::SharedCumulative(
 							usize_from_iter(&mut iter),
-							iter.map(|x| x.to_string()).collect::<Vec<_>>(),
+							iter.map(EventId::from).collect::<Vec<_>>(),
 						),
 					),
-					"M" => limits.insert(event, TimeLimit::Multi),
+					"M" => limits.insert(event.clone(), TimeLimit::Multi),
 					_ => panic!("Malformatted time limit for event: {}", event),
 				};
 			});
@@ -170,7 +178,7 @@ pub(crate) fn run(
 
 pub(crate) fn run_from_wcif(
 	wcif: &mut WcifContainer,
-	event: &str,
+	event: &EventId,
 	round: usize,
 	groups: Vec<Vec<(usize, usize)>>,
 	stages: &Stages,
@@ -179,11 +187,9 @@ pub(crate) fn run_from_wcif(
 	let (map, limit, competition) = crate::wcif::get_scorecard_info_for_round(wcif, event, round);
 
 	//Unwrap should not fail as the existence of this round is already confirmed at this point.
-	get_round_json(wcif, event, round)
-		.unwrap()
-		.scramble_set_count = groups.len();
+	let format = get_round_json(wcif, event, round).unwrap().format;
 	let mut limits = HashMap::new();
-	limits.insert(event, limit);
+	limits.insert(event.clone(), limit);
 
 	let mut k = groups
 		.into_iter()
@@ -192,6 +198,7 @@ pub(crate) fn run_from_wcif(
 			group.into_iter().map(move |(id, station)| Scorecard {
 				event,
 				round,
+				format: Some(format),
 				group: if stages.seperate_stages {
 					(n - 1) * stages.no as usize + 1 + station / stages.capacity as usize
 				} else {
@@ -288,6 +295,7 @@ pub(crate) fn blank_for_subsequent(wcif: &Wcif, stations: usize) -> Return {
 						scorecards.push(Scorecard {
 							event: event_name,
 							round: i,
+							format: Some(round.format),
 							group,
 							station: Some(j),
 							id: None,
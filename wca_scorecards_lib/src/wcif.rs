@@ -4,22 +4,26 @@ use wca_oauth::*;
 
 use scorecard_to_pdf::TimeLimit;
 
-pub fn get_rounds(wcif: &mut WcifContainer) -> Vec<(String, usize)> {
+pub fn get_rounds(wcif: &mut WcifContainer) -> Vec<(EventId, usize)> {
 	wcif.events_iter()
-		.flat_map(|event| event.rounds.iter().map(|round| round.id.to_string()))
-		.map(|str| {
-			let mut iter = str.split("-r");
-			(
-				iter.next().unwrap().to_string(),
-				iter.next().unwrap().parse().unwrap(),
-			)
+		.flat_map(|event| {
+			event.rounds.iter().enumerate().map(|(index, round)| {
+				// Rounds are listed in order, which is used when the id is not a valid round code.
+				let number = round
+					.id
+					.parse::<ActivityCode>()
+					.ok()
+					.and_then(|code| code.round_number())
+					.unwrap_or(index + 1);
+				(event.id.clone(), number)
+			})
 		})
 		.collect()
 }
 
 pub fn get_scorecard_info_for_round(
 	wcif: &mut WcifContainer,
-	event: &str,
+	event: &EventId,
 	round: usize,
 ) -> (HashMap<usize, String>, TimeLimit, String) {
 	let id_map = get_id_map(wcif);
@@ -27,7 +31,7 @@ pub fn get_scorecard_info_for_round(
 	(id_map, time_limit, wcif.get().name.clone())
 }
 
-pub fn get_time_limit(wcif: &mut WcifContainer, event: &str, round: usize) -> TimeLimit {
+pub fn get_time_limit(wcif: &mut WcifContainer, event: &EventId, round: usize) -> TimeLimit {
 	let round_json = get_round_json(wcif, event, round).unwrap();
 	match &round_json.time_limit {
 		None => TimeLimit::Multi,
@@ -35,7 +39,16 @@ pub fn get_time_limit(wcif: &mut WcifContainer, event: &str, round: usize) -> Ti
 			None => match v.cumulative_round_ids.len() {
 				0 => TimeLimit::Single(v.centiseconds),
 				1 => TimeLimit::Cumulative(v.centiseconds),
-				_ => TimeLimit::SharedCumulative(v.centiseconds, v.cumulative_round_ids.clone()),
+				_ => TimeLimit::SharedCumulative(
+					v.centiseconds,
+					v.cumulative_round_ids
+						.iter()
+						.map(|id| match id.parse::<ActivityCode>() {
+							Ok(ActivityCode::Event { event_id, .. }) => event_id,
+							_ => EventId::from(id.as_str()),
+						})
+						.collect(),
+				),
 			},
 			Some(ref c) => TimeLimit::Cutoff(
 				v.centiseconds,
@@ -51,7 +64,7 @@ pub fn get_time_limit(wcif: &mut WcifContainer, event: &str, round: usize) -> Ti
 
 pub fn wca_live_get_competitors_for_round(
 	wcif: &mut WcifContainer,
-	event: &str,
+	event: &EventId,
 	round: usize,
 ) -> (Vec<usize>, HashMap<usize, String>) {
 	let id_map = get_id_map(wcif);
@@ -89,7 +102,7 @@ pub fn wca_live_get_competitors_for_round(
 
 pub fn get_competitors_for_round(
 	wcif: &mut WcifContainer,
-	event: &str,
+	event: &EventId,
 	round: usize,
 ) -> (Vec<usize>, HashMap<usize, String>) {
 	let id_map = get_id_map(wcif);
@@ -101,11 +114,11 @@ pub fn get_competitors_for_round(
 
 pub(crate) fn get_round_json<'a>(
 	wcif: &'a mut WcifContainer,
-	event: &str,
+	event: &EventId,
 	round: usize,
 ) -> Option<&'a mut Round> {
-	let activity_id = format!("{}-r{}", event, round);
-	wcif.round_iter_mut().find(|round| round.id == activity_id)
+	let round_id = ActivityCode::round(event.clone(), round).to_string();
+	wcif.round_iter_mut().find(|round| round.id == round_id)
 }
 
 pub fn get_id_map(wcif: &WcifContainer) -> HashMap<usize, String> {
@@ -182,7 +195,7 @@ fn get_advancement(
 	)
 }
 
-pub(crate) fn get_registered_competitors(wcif: &Wcif, event_id: &str) -> Vec<usize> {
+pub(crate) fn get_registered_competitors(wcif: &Wcif, event_id: &EventId) -> Vec<usize> {
 	wcif.persons
 		.iter()
 		.filter(|person| {
@@ -202,8 +215,8 @@ pub(crate) fn get_registered_competitors(wcif: &Wcif, event_id: &str) -> Vec<usi
 
 /// Gets all ids that may compete in the round in seeding order. The participation source's
 /// advancement condition should be used to determine the actual advancement.
-fn get_participation(wcif: &Wcif, event_id: &str, round: u64) -> Option<Vec<usize>> {
-	let event = wcif.events.iter().find(|event| event.id == event_id)?;
+fn get_participation(wcif: &Wcif, event_id: &EventId, round: u64) -> Option<Vec<usize>> {
+	let event = wcif.events.iter().find(|event| event.id == *event_id)?;
 	let round = event.rounds.get((round - 1) as usize)?;
 	let participation_source = round
 		.participation_ruleset
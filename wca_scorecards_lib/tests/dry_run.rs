@@ -0,0 +1,62 @@
+use wca_oauth::{OAuth, WcifContainer};
+use wca_scorecards_lib::{generate_pdf_dry_run, GroupsError, ScorecardOrdering, Stages};
+
+fn wcif() -> WcifContainer {
+	let json = std::fs::read_to_string("../wca_oauth/tests/fixtures/competition.json").unwrap();
+	wca_oauth::parse(json).unwrap()
+}
+
+fn oauth() -> OAuth {
+	OAuth::builder(
+		"client".to_string(),
+		"urn:ietf:wg:oauth:2.0:oob".to_string(),
+	)
+	.implicit("token".to_string())
+}
+
+#[test]
+fn dry_run_summarizes_every_change() {
+	let mut wcif = wcif().add_oauth(oauth());
+	let (_, summary) = generate_pdf_dry_run(
+		&"333".into(),
+		2,
+		vec![vec![1], vec![2], vec![3]],
+		&mut wcif,
+		&Stages::new(1, 10, false),
+		ScorecardOrdering::Default,
+	)
+	.unwrap();
+
+	assert_eq!(summary.activities_added.len(), 3);
+	assert_eq!(summary.scramble_set_counts.len(), 1);
+	assert_eq!(summary.scramble_set_counts[0].round_id, "333-r2");
+	assert_eq!(summary.scramble_set_counts[0].after, 3);
+	assert!(summary
+		.to_string()
+		.contains("~ 333-r2 scramble sets: 1 -> 3\n"));
+
+	// The summary is exactly what the following patch sends.
+	assert_eq!(
+		Some(summary),
+		wcif.change_summary().unwrap(),
+		"the pdf changed the wcif after it was summarized"
+	);
+}
+
+#[test]
+fn dry_run_fails_for_unscheduled_rounds() {
+	let mut wcif = wcif().add_oauth(oauth());
+	let result = generate_pdf_dry_run(
+		&"444".into(),
+		1,
+		vec![vec![1]],
+		&mut wcif,
+		&Stages::new(1, 10, false),
+		ScorecardOrdering::Default,
+	);
+	match result {
+		Err(GroupsError::RoundNotScheduled { round: 1, .. }) => (),
+		Err(e) => panic!("expected the round to not be scheduled, got {e}"),
+		Ok(_) => panic!("expected the round to not be scheduled"),
+	}
+}